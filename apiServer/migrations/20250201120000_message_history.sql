@@ -0,0 +1,3 @@
+alter table messages add column created_at timestamptz not null default now();
+
+create index messages_channel_id_id_idx on messages (channel_id, id);
@@ -0,0 +1,10 @@
+alter table messages add column edited_at timestamptz;
+
+create table message_revisions (
+	id serial primary key,
+	message_id int references messages(id) not null,
+	message text not null,
+	created_at timestamptz not null default now()
+);
+
+create index message_revisions_message_id_idx on message_revisions (message_id);
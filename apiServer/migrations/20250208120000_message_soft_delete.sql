@@ -0,0 +1,2 @@
+alter table messages add column deleted_at timestamptz;
+alter table messages add column deleted_by int references users(id);
@@ -0,0 +1,3 @@
+alter table messages add column parent_id int references messages(id);
+
+create index messages_parent_id_id_idx on messages (parent_id, id);
@@ -0,0 +1,7 @@
+create table message_reactions (
+	message_id int references messages(id) not null,
+	user_id int references users(id) not null,
+	emoji varchar(32) not null,
+	created_at timestamptz not null default now(),
+	primary key (message_id, user_id, emoji)
+);
@@ -0,0 +1,6 @@
+alter table channel add column is_direct boolean not null default false;
+-- "lowerUserId:higherUserId", only set for direct conversations
+alter table channel add column direct_key varchar(32) unique;
+
+alter table channel drop constraint channel_name_key;
+create unique index channel_name_key on channel (name) where not is_direct;
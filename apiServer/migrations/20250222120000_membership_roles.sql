@@ -0,0 +1,7 @@
+create type channel_role as enum ('owner', 'admin', 'moderator', 'member', 'read_only');
+
+alter table membership add column role channel_role not null default 'member';
+
+update membership m set role = 'owner'
+from channel c
+where c.id = m.channel_id and c.admin_id = m.user_id and not c.is_direct;
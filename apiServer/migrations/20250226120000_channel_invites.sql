@@ -0,0 +1,13 @@
+create table channel_invites (
+	id serial primary key,
+	code varchar(32) unique not null,
+	channel_id int references channel(id) not null,
+	created_by int references users(id) not null,
+	expires_at timestamptz,
+	max_uses int,
+	uses int not null default 0,
+	revoked_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index channel_invites_channel_id_idx on channel_invites (channel_id);
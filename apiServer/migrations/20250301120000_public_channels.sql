@@ -0,0 +1,3 @@
+alter table channel add column is_public boolean not null default false;
+
+create index channel_is_public_idx on channel (id) where is_public;
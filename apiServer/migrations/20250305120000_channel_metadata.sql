@@ -0,0 +1,2 @@
+alter table channel add column topic varchar(250);
+alter table channel add column description varchar(1000);
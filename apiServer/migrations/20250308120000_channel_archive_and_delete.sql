@@ -0,0 +1,27 @@
+alter table channel add column archived_at timestamptz;
+
+-- deleting a channel takes its memberships, invites and messages with it,
+-- and deleting a message takes its replies, revisions and reactions
+alter table membership drop constraint membership_channel_id_fkey;
+alter table membership add constraint membership_channel_id_fkey
+	foreign key (channel_id) references channel(id) on delete cascade;
+
+alter table channel_invites drop constraint channel_invites_channel_id_fkey;
+alter table channel_invites add constraint channel_invites_channel_id_fkey
+	foreign key (channel_id) references channel(id) on delete cascade;
+
+alter table messages drop constraint messages_channel_id_fkey;
+alter table messages add constraint messages_channel_id_fkey
+	foreign key (channel_id) references channel(id) on delete cascade;
+
+alter table messages drop constraint messages_parent_id_fkey;
+alter table messages add constraint messages_parent_id_fkey
+	foreign key (parent_id) references messages(id) on delete cascade;
+
+alter table message_revisions drop constraint message_revisions_message_id_fkey;
+alter table message_revisions add constraint message_revisions_message_id_fkey
+	foreign key (message_id) references messages(id) on delete cascade;
+
+alter table message_reactions drop constraint message_reactions_message_id_fkey;
+alter table message_reactions add constraint message_reactions_message_id_fkey
+	foreign key (message_id) references messages(id) on delete cascade;
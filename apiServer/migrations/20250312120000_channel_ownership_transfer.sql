@@ -0,0 +1,5 @@
+alter table membership add column joined_at timestamptz not null default now();
+
+create index membership_channel_id_joined_at_idx on membership (channel_id, joined_at);
+
+alter table users add column deleted_at timestamptz;
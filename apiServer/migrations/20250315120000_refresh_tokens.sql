@@ -0,0 +1,12 @@
+create table refresh_tokens (
+	id serial primary key,
+	user_id int references users(id) not null,
+	family_id varchar(32) not null,
+	token_hash varchar(64) unique not null,
+	expires_at timestamptz not null,
+	used_at timestamptz,
+	revoked_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index refresh_tokens_family_id_idx on refresh_tokens (family_id);
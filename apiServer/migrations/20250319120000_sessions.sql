@@ -0,0 +1,17 @@
+create table sessions (
+	id varchar(32) primary key,
+	user_id int references users(id) not null,
+	revoked_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index sessions_user_id_idx on sessions (user_id);
+
+-- every refresh token family so far was one login, so it becomes that login's session
+insert into sessions (id, user_id, revoked_at, created_at)
+select family_id, min(user_id), max(revoked_at), min(created_at)
+from refresh_tokens
+group by family_id;
+
+alter table refresh_tokens
+	add constraint refresh_tokens_family_id_fkey foreign key (family_id) references sessions(id);
@@ -0,0 +1,5 @@
+alter table sessions
+	add column device_label varchar(50),
+	add column ip_address varchar(45),
+	add column user_agent varchar(255),
+	add column last_seen_at timestamptz not null default now();
@@ -0,0 +1,18 @@
+alter table users
+	add column email varchar(254) unique,
+	add column email_verified_at timestamptz;
+
+create type email_token_purpose as enum ('verify_email', 'reset_password');
+
+create table email_tokens (
+	id serial primary key,
+	user_id int references users(id) not null,
+	purpose email_token_purpose not null,
+	email varchar(254) not null,
+	token_hash varchar(64) unique not null,
+	expires_at timestamptz not null,
+	used_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index email_tokens_user_id_idx on email_tokens (user_id);
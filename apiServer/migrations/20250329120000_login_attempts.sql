@@ -0,0 +1,15 @@
+create type login_failure_reason as enum ('unknown_user', 'wrong_password');
+
+create table login_attempts (
+	id bigserial primary key,
+	username varchar(20) not null,
+	user_id int references users(id),
+	ip_address varchar(45),
+	user_agent varchar(255),
+	reason login_failure_reason not null,
+	locked_until timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index login_attempts_username_idx on login_attempts (username, created_at);
+create index login_attempts_ip_address_idx on login_attempts (ip_address, created_at);
@@ -0,0 +1,26 @@
+alter table users
+	add column totp_secret varchar(64),
+	add column totp_enabled_at timestamptz,
+	add column totp_last_used_step bigint;
+
+create table recovery_codes (
+	id serial primary key,
+	user_id int references users(id) not null,
+	code_hash varchar(64) unique not null,
+	used_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index recovery_codes_user_id_idx on recovery_codes (user_id);
+
+create table two_factor_challenges (
+	id serial primary key,
+	user_id int references users(id) not null,
+	token_hash varchar(64) unique not null,
+	device_label varchar(50),
+	expires_at timestamptz not null,
+	used_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+alter type login_failure_reason add value 'wrong_two_factor_code';
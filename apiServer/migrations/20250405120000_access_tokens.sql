@@ -0,0 +1,13 @@
+create table access_tokens (
+	id serial primary key,
+	user_id int references users(id) not null,
+	name varchar(50) not null,
+	token_hash varchar(64) unique not null,
+	scopes text[] not null,
+	expires_at timestamptz,
+	last_used_at timestamptz,
+	revoked_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index access_tokens_user_id_idx on access_tokens (user_id);
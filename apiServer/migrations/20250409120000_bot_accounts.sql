@@ -0,0 +1,14 @@
+create type user_kind as enum ('human', 'bot');
+
+alter table users
+	add column kind user_kind not null default 'human',
+	add column bot_owner_id int references users(id),
+	add column bot_token_hash varchar(64) unique,
+	alter column password drop not null;
+
+-- bots always have an owner and never a password, humans the other way round
+alter table users
+	add constraint users_bot_owner_check check ((kind = 'bot') = (bot_owner_id is not null)),
+	add constraint users_password_check check ((kind = 'human') = (password is not null));
+
+create index users_bot_owner_id_idx on users (bot_owner_id);
@@ -0,0 +1,15 @@
+-- every incoming webhook posts as its own integration user, which can not log in or join channels
+alter type user_kind add value 'integration';
+
+create table incoming_webhooks (
+	id serial primary key,
+	channel_id int references channel(id) on delete cascade not null,
+	integration_user_id int references users(id) unique not null,
+	created_by int references users(id) not null,
+	token_hash varchar(64) unique not null,
+	last_used_at timestamptz,
+	revoked_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index incoming_webhooks_channel_id_idx on incoming_webhooks (channel_id);
@@ -0,0 +1,32 @@
+create table outgoing_webhooks (
+	id serial primary key,
+	channel_id int references channel(id) on delete cascade not null,
+	created_by int references users(id) not null,
+	url varchar(2048) not null,
+	-- kept in plain text, every delivery is signed with it
+	secret varchar(64) not null,
+	revoked_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index outgoing_webhooks_channel_id_idx on outgoing_webhooks (channel_id);
+
+create type webhook_delivery_status as enum ('pending', 'delivered', 'failed');
+
+-- deliveries are queued in the same transaction as the message, so none are lost on a crash
+create table webhook_deliveries (
+	id serial primary key,
+	webhook_id int references outgoing_webhooks(id) on delete cascade not null,
+	event varchar(50) not null,
+	payload jsonb not null,
+	status webhook_delivery_status not null default 'pending',
+	attempts int not null default 0,
+	next_attempt_at timestamptz not null default now(),
+	last_status_code int,
+	last_error text,
+	delivered_at timestamptz,
+	created_at timestamptz not null default now()
+);
+
+create index webhook_deliveries_pending_idx on webhook_deliveries (next_attempt_at) where status = 'pending';
+create index webhook_deliveries_webhook_id_idx on webhook_deliveries (webhook_id, id);
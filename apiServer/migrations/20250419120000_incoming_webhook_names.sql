@@ -0,0 +1,11 @@
+-- the display name lives on the webhook, integration users get an internal
+-- username so webhooks no longer take names from the login namespace
+alter table incoming_webhooks add column name varchar(50);
+
+update incoming_webhooks w set name = u.username
+from users u
+where u.id = w.integration_user_id;
+
+alter table incoming_webhooks alter column name set not null;
+
+update users set username = 'webhook_' || id where kind = 'integration';
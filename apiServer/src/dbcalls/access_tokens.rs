@@ -0,0 +1,92 @@
+use sqlx::prelude::FromRow;
+
+use crate::{
+    models::access_token::{AccessTokenDb, AccessTokenScope},
+    tokens::{generate_random_token::generate_random_token, hash_token::hash_token},
+    AppState,
+};
+
+// tells access tokens apart from jwts, and makes leaked ones easy to grep for
+pub const ACCESS_TOKEN_PREFIX: &str = "pat_";
+// last_used_at is only kept to the minute, same as sessions
+const LAST_USED_RESOLUTION_IN_SECONDS: f64 = 60.0;
+
+#[derive(FromRow)]
+pub struct ActiveAccessToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub username: String,
+    pub scopes: Vec<String>,
+}
+
+impl ActiveAccessToken {
+    pub fn allows(&self, scope: AccessTokenScope) -> bool {
+        self.scopes.iter().any(|granted| granted == scope.as_str())
+    }
+}
+
+/// Creates a token for scripts, the plain token is only returned here.
+pub async fn issue_access_token(
+    app_state: &AppState,
+    user_id: i32,
+    name: &str,
+    scopes: &[AccessTokenScope],
+    expires_in_days: Option<i64>,
+) -> Result<(AccessTokenDb, String), String> {
+    let token = format!("{}{}", ACCESS_TOKEN_PREFIX, generate_random_token(40));
+    let scopes: Vec<&str> = scopes.iter().map(|scope| scope.as_str()).collect();
+
+    let insert_result = sqlx::query_as::<_, AccessTokenDb>(
+        "insert into access_tokens(user_id, name, token_hash, scopes, expires_at)
+        values ($1, $2, $3, $4, now() + make_interval(days => $5))
+        returning *",
+    )
+    .bind(user_id)
+    .bind(name)
+    .bind(hash_token(&token))
+    .bind(scopes)
+    .bind(expires_in_days.map(|days| days as i32))
+    .fetch_one(&app_state.database)
+    .await;
+
+    if insert_result.is_err() {
+        return Err("Issue creating the access token".to_string());
+    }
+
+    Ok((insert_result.unwrap(), token))
+}
+
+/// Looks up a token presented to the api, `None` when it is unknown,
+/// revoked, expired or its owner is gone.
+pub async fn find_active_access_token(
+    app_state: &AppState,
+    token: &str,
+) -> Result<Option<ActiveAccessToken>, String> {
+    let token_result = sqlx::query_as::<_, ActiveAccessToken>(
+        "select access_tokens.id, access_tokens.user_id, users.username, access_tokens.scopes
+        from access_tokens
+        join users on users.id = access_tokens.user_id
+        where access_tokens.token_hash = $1
+        and access_tokens.revoked_at is null
+        and (access_tokens.expires_at is null or access_tokens.expires_at > now())
+        and users.deleted_at is null",
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&app_state.database)
+    .await;
+
+    token_result.map_err(|_| "Issue talking to the database".to_string())
+}
+
+/// Bumps last_used_at, at most once a minute per token.
+pub async fn touch_access_token(app_state: &AppState, access_token_id: i32) {
+    let _ = sqlx::query(
+        "update access_tokens set last_used_at = now()
+        where id = $1
+        and (last_used_at is null or last_used_at < now() - make_interval(secs => $2))",
+    )
+    .bind(access_token_id)
+    .bind(LAST_USED_RESOLUTION_IN_SECONDS)
+    .execute(&app_state.database)
+    .await;
+}
@@ -0,0 +1,76 @@
+use sqlx::{prelude::FromRow, PgConnection};
+
+use crate::{
+    models::access_token::AccessTokenScope,
+    tokens::{generate_random_token::generate_random_token, hash_token::hash_token},
+    AppState,
+};
+
+pub const BOT_TOKEN_PREFIX: &str = "bot_";
+
+// bots read channels and post in the ones they were added to, joining or
+// managing channels is left to their owner
+pub const BOT_SCOPES: [AccessTokenScope; 4] = [
+    AccessTokenScope::ChannelsRead,
+    AccessTokenScope::MessagesRead,
+    AccessTokenScope::MessagesWrite,
+    AccessTokenScope::UserRead,
+];
+
+#[derive(FromRow)]
+pub struct ActiveBot {
+    pub id: i32,
+    pub username: String,
+}
+
+pub fn generate_bot_token() -> String {
+    format!("{}{}", BOT_TOKEN_PREFIX, generate_random_token(40))
+}
+
+/// Looks up the bot a token belongs to, `None` when the token was replaced,
+/// the bot was removed or its owner deleted their account.
+pub async fn find_bot_by_token(
+    app_state: &AppState,
+    token: &str,
+) -> Result<Option<ActiveBot>, String> {
+    let bot_result = sqlx::query_as::<_, ActiveBot>(
+        "select bots.id, bots.username from users bots
+        join users owners on owners.id = bots.bot_owner_id
+        where bots.bot_token_hash = $1 and bots.kind = 'bot'
+        and bots.deleted_at is null and owners.deleted_at is null",
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&app_state.database)
+    .await;
+
+    bot_result.map_err(|_| "Issue talking to the database".to_string())
+}
+
+/// Takes the bot out of every channel and deletes it, returning the channels
+/// it left so members can be told.
+pub async fn remove_bot(
+    connection: &mut PgConnection,
+    bot_id: i32,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let left_channels: Vec<(i32,)> = sqlx::query_as(
+        "delete from membership m using channel c
+        where c.id = m.channel_id and m.user_id = $1 and not c.is_direct
+        returning m.channel_id",
+    )
+    .bind(bot_id)
+    .fetch_all(&mut *connection)
+    .await?;
+
+    sqlx::query(
+        "update users set deleted_at = now(), bot_token_hash = null
+        where id = $1 and kind = 'bot'",
+    )
+    .bind(bot_id)
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(left_channels
+        .into_iter()
+        .map(|(channel_id,)| channel_id)
+        .collect())
+}
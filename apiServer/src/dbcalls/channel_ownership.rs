@@ -0,0 +1,58 @@
+use sqlx::PgConnection;
+
+/// Hands the channel to `new_owner_id`, the current owner is kept on as an admin.
+/// Has to run inside a transaction so `admin_id` and the roles never disagree.
+pub async fn transfer_channel_ownership(
+    connection: &mut PgConnection,
+    channel_id: i32,
+    new_owner_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("update membership set role = 'admin' where channel_id = $1 and role = 'owner'")
+        .bind(channel_id)
+        .execute(&mut *connection)
+        .await?;
+
+    sqlx::query("update membership set role = 'owner' where channel_id = $1 and user_id = $2")
+        .bind(channel_id)
+        .bind(new_owner_id)
+        .execute(&mut *connection)
+        .await?;
+
+    sqlx::query("update channel set admin_id = $1 where id = $2")
+        .bind(new_owner_id)
+        .bind(channel_id)
+        .execute(&mut *connection)
+        .await?;
+
+    Ok(())
+}
+
+/// Promotes the member who joined first (highest role wins a tie) to owner,
+/// `None` means no other human is left in the channel.
+pub async fn promote_longest_standing_member(
+    connection: &mut PgConnection,
+    channel_id: i32,
+    leaving_owner_id: i32,
+) -> Result<Option<i32>, sqlx::Error> {
+    // channel_role is declared from owner down, so ordering by it puts higher roles first.
+    // bots can not own channels, a channel with only bots left is archived instead
+    let successor: Option<(i32,)> = sqlx::query_as(
+        "select membership.user_id from membership
+        join users on users.id = membership.user_id
+        where membership.channel_id = $1 and membership.user_id <> $2 and users.kind = 'human'
+        order by membership.joined_at, membership.role, membership.user_id
+        limit 1",
+    )
+    .bind(channel_id)
+    .bind(leaving_owner_id)
+    .fetch_optional(&mut *connection)
+    .await?;
+
+    match successor {
+        None => Ok(None),
+        Some((successor_id,)) => {
+            transfer_channel_ownership(connection, channel_id, successor_id).await?;
+            Ok(Some(successor_id))
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use actix_web::HttpResponse;
+use sqlx::{prelude::FromRow, PgExecutor};
+
+use crate::{
+    models::membership::{ChannelRole, MembershipDb},
+    responses::general_error::GeneralError,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub enum ChannelPermission {
+    ReadMessages,
+    SendMessages,
+    ReactToMessages,
+    DeleteOwnMessage,
+    DeleteAnyMessage,
+    RemoveMembers,
+    AddMembers,
+    ManageInvites,
+    ManageWebhooks,
+    ManageRoles,
+    UpdateChannel,
+    TransferOwnership,
+    ArchiveChannel,
+    DeleteChannel,
+}
+
+impl ChannelPermission {
+    fn minimum_role(&self) -> ChannelRole {
+        match self {
+            ChannelPermission::ReadMessages => ChannelRole::ReadOnly,
+            ChannelPermission::SendMessages => ChannelRole::Member,
+            ChannelPermission::ReactToMessages => ChannelRole::Member,
+            ChannelPermission::DeleteOwnMessage => ChannelRole::ReadOnly,
+            ChannelPermission::DeleteAnyMessage => ChannelRole::Moderator,
+            ChannelPermission::RemoveMembers => ChannelRole::Moderator,
+            ChannelPermission::AddMembers => ChannelRole::Admin,
+            ChannelPermission::ManageInvites => ChannelRole::Admin,
+            ChannelPermission::ManageWebhooks => ChannelRole::Admin,
+            ChannelPermission::ManageRoles => ChannelRole::Admin,
+            ChannelPermission::UpdateChannel => ChannelRole::Admin,
+            ChannelPermission::TransferOwnership => ChannelRole::Owner,
+            ChannelPermission::ArchiveChannel => ChannelRole::Owner,
+            ChannelPermission::DeleteChannel => ChannelRole::Owner,
+        }
+    }
+
+    /// Archived channels are read-only, only reading, handing over and retiring the channel stay open.
+    fn allowed_when_archived(&self) -> bool {
+        matches!(
+            self,
+            ChannelPermission::ReadMessages
+                | ChannelPermission::TransferOwnership
+                | ChannelPermission::ArchiveChannel
+                | ChannelPermission::DeleteChannel
+        )
+    }
+}
+
+#[derive(FromRow)]
+struct MembershipWithChannelState {
+    #[sqlx(flatten)]
+    membership: MembershipDb,
+    is_archived: bool,
+}
+
+pub enum PermissionCheck {
+    Allowed(MembershipDb),
+    NotMember,
+    Forbidden,
+    Archived,
+}
+
+pub fn role_has_permission(role: ChannelRole, permission: ChannelPermission) -> bool {
+    role.rank() >= permission.minimum_role().rank()
+}
+
+pub async fn check_channel_permission<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: i32,
+    channel_id: i32,
+    permission: ChannelPermission,
+) -> Result<PermissionCheck, String> {
+    let membership_result = sqlx::query_as::<_, MembershipWithChannelState>(
+        "select m.*, c.archived_at is not null as is_archived
+        from membership m join channel c on c.id = m.channel_id
+        where m.user_id=$1 and m.channel_id=$2",
+    )
+    .bind(user_id)
+    .bind(channel_id)
+    .fetch_optional(executor)
+    .await;
+
+    match membership_result {
+        Err(_) => Err("Issue talking to the database".to_string()),
+        Ok(None) => Ok(PermissionCheck::NotMember),
+        Ok(Some(membership_with_state)) => {
+            if !role_has_permission(membership_with_state.membership.role, permission) {
+                Ok(PermissionCheck::Forbidden)
+            } else if membership_with_state.is_archived && !permission.allowed_when_archived() {
+                Ok(PermissionCheck::Archived)
+            } else {
+                Ok(PermissionCheck::Allowed(membership_with_state.membership))
+            }
+        }
+    }
+}
+
+/// Maps a failed permission check to the response the route should return,
+/// `None` means the caller is allowed to continue.
+pub fn permission_error_response(
+    permission_result: &Result<PermissionCheck, String>,
+) -> Option<HttpResponse> {
+    match permission_result {
+        Ok(PermissionCheck::Allowed(_)) => None,
+        Ok(PermissionCheck::NotMember) => Some(HttpResponse::Unauthorized().json(GeneralError {
+            message: "You are not a member of this channel".to_string(),
+        })),
+        Ok(PermissionCheck::Forbidden) => Some(HttpResponse::Unauthorized().json(GeneralError {
+            message: "Your role in this channel does not allow this".to_string(),
+        })),
+        Ok(PermissionCheck::Archived) => Some(HttpResponse::BadRequest().json(GeneralError {
+            message: "This channel is archived".to_string(),
+        })),
+        Err(err_string) => Some(HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string.clone(),
+        })),
+    }
+}
@@ -7,17 +7,21 @@ use crate::AppState;
 struct ExistsResult {
     exists: bool,
 }
-pub async fn check_user_exists(
+
+/// Postgres side of the token check, it still holds when redis is unavailable
+/// because a logout or revoke always lands in the sessions table.
+pub async fn check_session_active(
     user_id: i32,
-    username: &str,
+    session_id: &str,
     app_state: &AppState,
 ) -> Result<bool, String> {
     let query_result = sqlx::query_as::<_, ExistsResult>(
         "SELECT EXISTS(
-        SELECT * FROM users WHERE username=$1 and id=$2
+        SELECT * FROM sessions s JOIN users u ON u.id = s.user_id
+        WHERE s.id=$1 and s.user_id=$2 and s.revoked_at is null and u.deleted_at is null
     ) AS exists", // Alias the result as "exists"
     )
-    .bind(username)
+    .bind(session_id)
     .bind(user_id)
     .fetch_optional(&app_state.database)
     .await;
@@ -25,10 +29,10 @@ pub async fn check_user_exists(
     match query_result {
         Err(_) => Err("Issue talking to the database".to_string()),
         Ok(row_data) => match row_data {
-            None => Err("Issue finding the user".to_string()),
+            None => Err("Issue finding the session".to_string()),
             Some(res_struct) => {
                 if !res_struct.exists {
-                    Err("User not found".to_string())
+                    Err("Session expired, login again".to_string())
                 } else {
                     Ok(true)
                 }
@@ -0,0 +1,80 @@
+use sqlx::PgConnection;
+
+use crate::{
+    models::email_token::{EmailTokenDb, EmailTokenPurpose},
+    tokens::{generate_random_token::generate_random_token, hash_token::hash_token},
+    AppState,
+};
+
+pub const EMAIL_VERIFICATION_EXPIRY_IN_SECONDS: i64 = 86400; // 24 hours
+pub const PASSWORD_RESET_EXPIRY_IN_SECONDS: i64 = 3600; // 1 hour
+
+impl EmailTokenPurpose {
+    fn expiry_in_seconds(&self) -> i64 {
+        match self {
+            EmailTokenPurpose::VerifyEmail => EMAIL_VERIFICATION_EXPIRY_IN_SECONDS,
+            EmailTokenPurpose::ResetPassword => PASSWORD_RESET_EXPIRY_IN_SECONDS,
+        }
+    }
+}
+
+/// Creates a single-use token for `email`, any older unused token with the
+/// same purpose stops working so only the latest email is valid.
+pub async fn create_email_token(
+    app_state: &AppState,
+    user_id: i32,
+    email: &str,
+    purpose: EmailTokenPurpose,
+) -> Result<String, String> {
+    let token = generate_random_token(64);
+
+    let supersede_result = sqlx::query(
+        "update email_tokens set used_at = now()
+        where user_id = $1 and purpose = $2 and used_at is null",
+    )
+    .bind(user_id)
+    .bind(purpose)
+    .execute(&app_state.database)
+    .await;
+
+    if supersede_result.is_err() {
+        return Err("Issue talking to the database".to_string());
+    }
+
+    let insert_result = sqlx::query(
+        "insert into email_tokens(user_id, purpose, email, token_hash, expires_at)
+        values ($1, $2, $3, $4, now() + make_interval(secs => $5))",
+    )
+    .bind(user_id)
+    .bind(purpose)
+    .bind(email)
+    .bind(hash_token(&token))
+    .bind(purpose.expiry_in_seconds() as f64)
+    .execute(&app_state.database)
+    .await;
+
+    if insert_result.is_err() {
+        return Err("Issue generating the token".to_string());
+    }
+
+    Ok(token)
+}
+
+/// Spends a token, `None` means it is unknown, expired, already used or meant
+/// for something else. Runs on the caller's transaction so the token is only
+/// spent if what it unlocks goes through too.
+pub async fn consume_email_token(
+    connection: &mut PgConnection,
+    token: &str,
+    purpose: EmailTokenPurpose,
+) -> Result<Option<EmailTokenDb>, sqlx::Error> {
+    sqlx::query_as::<_, EmailTokenDb>(
+        "update email_tokens set used_at = now()
+        where token_hash = $1 and purpose = $2 and used_at is null and expires_at > now()
+        returning *",
+    )
+    .bind(hash_token(token))
+    .bind(purpose)
+    .fetch_optional(connection)
+    .await
+}
@@ -0,0 +1,62 @@
+use sqlx::PgConnection;
+
+use crate::{
+    models::incoming_webhook::IncomingWebhookDb,
+    tokens::{generate_random_token::generate_random_token, hash_token::hash_token},
+    AppState,
+};
+
+pub const INCOMING_WEBHOOK_PREFIX: &str = "whk_";
+// integration users are named after their id, humans and bots can not pick these
+pub const INTEGRATION_USERNAME_PREFIX: &str = "webhook_";
+
+/// Creates the webhook together with the integration user it posts as,
+/// the plain token is only returned here.
+pub async fn create_incoming_webhook(
+    connection: &mut PgConnection,
+    channel_id: i32,
+    created_by: i32,
+    name: &str,
+) -> Result<(IncomingWebhookDb, String), sqlx::Error> {
+    let token = format!("{}{}", INCOMING_WEBHOOK_PREFIX, generate_random_token(40));
+
+    let (integration_user_id,): (i32,) = sqlx::query_as(
+        "with next_user as (select nextval(pg_get_serial_sequence('users', 'id'))::int as id)
+        insert into users(id, username, kind)
+        select id, $1 || id, 'integration' from next_user
+        returning id",
+    )
+    .bind(INTEGRATION_USERNAME_PREFIX)
+    .fetch_one(&mut *connection)
+    .await?;
+
+    let new_webhook = sqlx::query_as::<_, IncomingWebhookDb>(
+        "insert into incoming_webhooks(channel_id, integration_user_id, created_by, token_hash, name)
+        values ($1, $2, $3, $4, $5)
+        returning *",
+    )
+    .bind(channel_id)
+    .bind(integration_user_id)
+    .bind(created_by)
+    .bind(hash_token(&token))
+    .bind(name)
+    .fetch_one(&mut *connection)
+    .await?;
+
+    Ok((new_webhook, token))
+}
+
+/// Looks up the webhook a posted token belongs to, `None` when it is unknown or revoked.
+pub async fn find_active_incoming_webhook(
+    app_state: &AppState,
+    token: &str,
+) -> Result<Option<IncomingWebhookDb>, String> {
+    let webhook_result = sqlx::query_as::<_, IncomingWebhookDb>(
+        "select * from incoming_webhooks where token_hash = $1 and revoked_at is null",
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&app_state.database)
+    .await;
+
+    webhook_result.map_err(|_| "Issue talking to the database".to_string())
+}
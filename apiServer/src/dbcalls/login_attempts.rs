@@ -0,0 +1,36 @@
+use log::warn;
+
+use crate::{models::login_attempt::LoginFailureReason, AppState};
+
+pub struct FailedLogin<'a> {
+    pub username: &'a str,
+    pub user_id: Option<i32>,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub reason: LoginFailureReason,
+    pub locked_for_seconds: Option<u64>,
+}
+
+/// Adds a failed login to the audit trail. Losing a row is not worth failing
+/// the request over, so errors are only logged.
+pub async fn record_failed_login(app_state: &AppState, failed_login: FailedLogin<'_>) {
+    let insert_result = sqlx::query(
+        "insert into login_attempts(username, user_id, ip_address, user_agent, reason, locked_until)
+        values ($1, $2, left($3, 45), left($4, 255), $5, now() + make_interval(secs => $6))",
+    )
+    .bind(failed_login.username)
+    .bind(failed_login.user_id)
+    .bind(failed_login.ip_address)
+    .bind(failed_login.user_agent)
+    .bind(failed_login.reason)
+    .bind(failed_login.locked_for_seconds.map(|seconds| seconds as f64))
+    .execute(&app_state.database)
+    .await;
+
+    if let Err(err) = insert_result {
+        warn!(
+            "Issue recording the failed login for {}: {}",
+            failed_login.username, err
+        );
+    }
+}
@@ -1 +1,11 @@
-pub mod check_user_exists;
+pub mod access_tokens;
+pub mod bots;
+pub mod channel_ownership;
+pub mod channel_permission;
+pub mod check_session_active;
+pub mod email_tokens;
+pub mod incoming_webhooks;
+pub mod login_attempts;
+pub mod outgoing_webhooks;
+pub mod sessions;
+pub mod two_factor;
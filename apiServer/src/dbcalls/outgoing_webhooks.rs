@@ -0,0 +1,142 @@
+use sqlx::{prelude::FromRow, PgConnection, Pool, Postgres};
+
+use crate::tokens::generate_random_token::generate_random_token;
+
+pub const OUTGOING_WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+pub const MESSAGE_CREATED_EVENT: &str = "message_created";
+
+#[derive(FromRow)]
+pub struct DueDelivery {
+    pub id: i32,
+    pub event: String,
+    pub payload: sqlx::types::JsonValue,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+pub fn generate_webhook_secret() -> String {
+    format!(
+        "{}{}",
+        OUTGOING_WEBHOOK_SECRET_PREFIX,
+        generate_random_token(40)
+    )
+}
+
+/// Queues a message_created delivery for every active outgoing webhook of the
+/// message's channel, meant to run in the transaction that inserted the message.
+pub async fn enqueue_message_created(
+    connection: &mut PgConnection,
+    message_id: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "insert into webhook_deliveries(webhook_id, event, payload)
+        select w.id, $2, jsonb_build_object(
+            'event', $2,
+            'channel_id', m.channel_id,
+            'message_id', m.id,
+            'sender_id', m.sender_id,
+            'username', coalesce(iw.name, u.username),
+            'message', m.message,
+            'parent_id', m.parent_id,
+            'created_at', m.created_at
+        )
+        from messages m
+        join users u on u.id = m.sender_id
+        left join incoming_webhooks iw on iw.integration_user_id = m.sender_id
+        join outgoing_webhooks w on w.channel_id = m.channel_id and w.revoked_at is null
+        where m.id = $1",
+    )
+    .bind(message_id)
+    .bind(MESSAGE_CREATED_EVENT)
+    .execute(connection)
+    .await?;
+
+    Ok(())
+}
+
+/// Gives up the pending deliveries of revoked webhooks. A message sent while
+/// its webhook was being revoked can queue one after the revoke cancelled the rest.
+pub async fn fail_revoked_deliveries(database: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "update webhook_deliveries d set status = 'failed', last_error = 'Webhook revoked'
+        from outgoing_webhooks w
+        where w.id = d.webhook_id and w.revoked_at is not null and d.status = 'pending'",
+    )
+    .execute(database)
+    .await?;
+
+    Ok(())
+}
+
+/// Claims up to `limit` deliveries that are due by pushing their next attempt
+/// past the lease, so a worker that dies mid request only delays them.
+pub async fn claim_due_deliveries(
+    database: &Pool<Postgres>,
+    limit: i64,
+    lease_in_seconds: f64,
+) -> Result<Vec<DueDelivery>, sqlx::Error> {
+    // revoked webhooks are left out before the limit, so their rows can not fill every batch
+    sqlx::query_as::<_, DueDelivery>(
+        "update webhook_deliveries d set next_attempt_at = now() + make_interval(secs => $2)
+        from outgoing_webhooks w
+        where w.id = d.webhook_id and d.id in (
+            select due.id from webhook_deliveries due
+            join outgoing_webhooks active on active.id = due.webhook_id and active.revoked_at is null
+            where due.status = 'pending' and due.next_attempt_at <= now()
+            order by due.next_attempt_at
+            limit $1
+            for update of due skip locked
+        )
+        returning d.id, d.event, d.payload, d.attempts, w.url, w.secret",
+    )
+    .bind(limit)
+    .bind(lease_in_seconds)
+    .fetch_all(database)
+    .await
+}
+
+pub async fn mark_delivered(
+    database: &Pool<Postgres>,
+    delivery_id: i32,
+    status_code: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "update webhook_deliveries
+        set status = 'delivered', attempts = attempts + 1, last_status_code = $2,
+        last_error = null, delivered_at = now()
+        where id = $1 and status = 'pending'",
+    )
+    .bind(delivery_id)
+    .bind(status_code)
+    .execute(database)
+    .await?;
+
+    Ok(())
+}
+
+/// Records a failed attempt, `retry_in_seconds` of `None` gives the delivery up.
+/// Deliveries cancelled while in flight stay cancelled.
+pub async fn mark_attempt_failed(
+    database: &Pool<Postgres>,
+    delivery_id: i32,
+    status_code: Option<i32>,
+    error: &str,
+    retry_in_seconds: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "update webhook_deliveries
+        set attempts = attempts + 1, last_status_code = $2, last_error = $3,
+        status = case when $4::bigint is null then 'failed' else 'pending' end::webhook_delivery_status,
+        next_attempt_at = now() + make_interval(secs => coalesce($4, 0)::double precision)
+        where id = $1 and status = 'pending'",
+    )
+    .bind(delivery_id)
+    .bind(status_code)
+    .bind(error)
+    .bind(retry_in_seconds)
+    .execute(database)
+    .await?;
+
+    Ok(())
+}
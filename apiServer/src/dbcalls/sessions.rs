@@ -0,0 +1,103 @@
+use redis::Commands;
+
+use crate::{
+    tokens::{
+        generate_random_token::generate_random_token, refresh_token::revoke_refresh_token_family,
+    },
+    AppState,
+};
+
+// last_seen_at is only kept to the minute, no need to write it on every request
+const LAST_SEEN_RESOLUTION_IN_SECONDS: u64 = 60;
+
+pub struct SessionDetails {
+    pub device_label: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// The redis copy of a session's current access token.
+pub fn session_redis_key(user_id: i32, session_id: &str) -> String {
+    format!("auth:{}:{}", user_id, session_id)
+}
+
+/// Starts a session for a fresh login, its id is also the refresh token family.
+pub async fn create_session(
+    app_state: &AppState,
+    user_id: i32,
+    session_details: SessionDetails,
+) -> Result<String, String> {
+    let session_id = generate_random_token(32);
+
+    let insert_result = sqlx::query(
+        "insert into sessions(id, user_id, device_label, ip_address, user_agent)
+        values ($1, $2, $3, left($4, 45), left($5, 255))",
+    )
+    .bind(&session_id)
+    .bind(user_id)
+    .bind(session_details.device_label)
+    .bind(session_details.ip_address)
+    .bind(session_details.user_agent)
+    .execute(&app_state.database)
+    .await;
+
+    if insert_result.is_err() {
+        return Err("Issue creating the session".to_string());
+    }
+
+    Ok(session_id)
+}
+
+/// Bumps last_seen_at, at most once a minute per session.
+pub async fn touch_session(app_state: &AppState, session_id: &str) {
+    if let Ok(mut redis_connection) = app_state.redis_pool.get() {
+        let first_in_window: Result<Option<String>, _> = redis::cmd("SET")
+            .arg(format!("last_seen:{}", session_id))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(LAST_SEEN_RESOLUTION_IN_SECONDS)
+            .query(&mut *redis_connection);
+        if let Ok(None) = first_in_window {
+            return;
+        }
+    }
+
+    let _ = sqlx::query(
+        "update sessions set last_seen_at = now()
+        where id = $1 and last_seen_at < now() - make_interval(secs => $2)",
+    )
+    .bind(session_id)
+    .bind(LAST_SEEN_RESOLUTION_IN_SECONDS as f64)
+    .execute(&app_state.database)
+    .await;
+}
+
+/// Revokes the session in postgres first so the check holds even when redis is
+/// down, then drops its refresh tokens and the cached access token.
+pub async fn revoke_session(
+    app_state: &AppState,
+    user_id: i32,
+    session_id: &str,
+) -> Result<(), String> {
+    let revoke_result = sqlx::query(
+        "update sessions set revoked_at = now()
+        where id = $1 and user_id = $2 and revoked_at is null",
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .execute(&app_state.database)
+    .await;
+
+    if revoke_result.is_err() {
+        return Err("Issue revoking the session".to_string());
+    }
+
+    revoke_refresh_token_family(app_state, session_id).await?;
+
+    if let Ok(mut redis_connection) = app_state.redis_pool.get() {
+        let _: Result<(), _> = redis_connection.del(session_redis_key(user_id, session_id));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use sqlx::PgConnection;
+
+use crate::{
+    models::two_factor_challenge::TwoFactorChallengeDb,
+    tokens::{
+        generate_random_token::generate_random_token, hash_token::hash_token,
+        totp::matching_totp_step,
+    },
+    AppState,
+};
+
+pub const RECOVERY_CODE_COUNT: usize = 10;
+pub const TWO_FACTOR_CHALLENGE_EXPIRY_IN_SECONDS: i64 = 300; // 5 minutes
+
+/// Recovery codes are handed out as `xxxxx-xxxxx`, the dash and case are
+/// ignored when one is typed back in.
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Replaces every recovery code of the user with a fresh set. Only the hashes
+/// are stored, the plain codes are returned to be shown once.
+pub async fn replace_recovery_codes(
+    connection: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query("delete from recovery_codes where user_id = $1")
+        .bind(user_id)
+        .execute(&mut *connection)
+        .await?;
+
+    let mut recovery_codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+    for _ in 0..RECOVERY_CODE_COUNT {
+        let code = generate_random_token(10).to_lowercase();
+
+        sqlx::query("insert into recovery_codes(user_id, code_hash) values ($1, $2)")
+            .bind(user_id)
+            .bind(hash_token(&code))
+            .execute(&mut *connection)
+            .await?;
+
+        recovery_codes.push(format!("{}-{}", &code[..5], &code[5..]));
+    }
+
+    Ok(recovery_codes)
+}
+
+/// Checks a code from the authenticator app, or spends a recovery code when
+/// it is not one. Locks the user row so the same totp code can not be used
+/// twice by racing requests.
+pub async fn verify_second_factor(
+    connection: &mut PgConnection,
+    user_id: i32,
+    code: &str,
+) -> Result<bool, String> {
+    let user_result = sqlx::query_as::<_, (String, Option<String>, Option<i64>)>(
+        "select username, totp_secret, totp_last_used_step from users
+        where id = $1 and totp_enabled_at is not null and deleted_at is null
+        for update",
+    )
+    .bind(user_id)
+    .fetch_optional(&mut *connection)
+    .await
+    .map_err(|_| "Issue talking to the database".to_string())?;
+
+    let Some((username, Some(totp_secret), last_used_step)) = user_result else {
+        return Ok(false);
+    };
+
+    if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+        let Some(step) = matching_totp_step(&totp_secret, &username, code, last_used_step)? else {
+            return Ok(false);
+        };
+
+        sqlx::query("update users set totp_last_used_step = $1 where id = $2")
+            .bind(step)
+            .bind(user_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(|_| "Issue talking to the database".to_string())?;
+
+        return Ok(true);
+    }
+
+    let spent_code = sqlx::query(
+        "update recovery_codes set used_at = now()
+        where user_id = $1 and code_hash = $2 and used_at is null",
+    )
+    .bind(user_id)
+    .bind(hash_token(&normalize_recovery_code(code)))
+    .execute(&mut *connection)
+    .await
+    .map_err(|_| "Issue talking to the database".to_string())?;
+
+    Ok(spent_code.rows_affected() == 1)
+}
+
+/// Starts the second login step after a correct password. The token stands in
+/// for the password until the code is checked.
+pub async fn create_two_factor_challenge(
+    app_state: &AppState,
+    user_id: i32,
+    device_label: Option<String>,
+) -> Result<String, String> {
+    let token = generate_random_token(64);
+
+    let insert_result = sqlx::query(
+        "insert into two_factor_challenges(user_id, token_hash, device_label, expires_at)
+        values ($1, $2, $3, now() + make_interval(secs => $4))",
+    )
+    .bind(user_id)
+    .bind(hash_token(&token))
+    .bind(device_label)
+    .bind(TWO_FACTOR_CHALLENGE_EXPIRY_IN_SECONDS as f64)
+    .execute(&app_state.database)
+    .await;
+
+    if insert_result.is_err() {
+        return Err("Issue starting the two factor login".to_string());
+    }
+
+    Ok(token)
+}
+
+/// Spends a login challenge, `None` means it is unknown, expired or already
+/// used. A wrong code rolls the caller's transaction back so the challenge
+/// can be tried again until it expires.
+pub async fn consume_two_factor_challenge(
+    connection: &mut PgConnection,
+    token: &str,
+) -> Result<Option<TwoFactorChallengeDb>, sqlx::Error> {
+    sqlx::query_as::<_, TwoFactorChallengeDb>(
+        "update two_factor_challenges set used_at = now()
+        where token_hash = $1 and used_at is null and expires_at > now()
+        returning *",
+    )
+    .bind(hash_token(token))
+    .fetch_optional(connection)
+    .await
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use redis::Commands;
+
+// failures are forgotten once nothing failed for this long
+const FAILURE_WINDOW_IN_SECONDS: u64 = 15 * 60;
+const LOCKOUT_IN_SECONDS: u64 = 15 * 60;
+// every login goes through here, so it gives up on redis quickly and counts in memory
+const REDIS_TIMEOUT_IN_MILLISECONDS: u64 = 250;
+// the in memory fallback is pruned once it tracks this many keys
+const MAX_MEMORY_ENTRIES: usize = 10_000;
+
+struct Limits {
+    // failures allowed before every further one adds a delay
+    free_attempts: u64,
+    // failures before the full lockout
+    max_attempts: u64,
+}
+
+const USERNAME_LIMITS: Limits = Limits {
+    free_attempts: 3,
+    max_attempts: 10,
+};
+
+// many users can share an address behind a nat, so it gets more room
+const IP_LIMITS: Limits = Limits {
+    free_attempts: 20,
+    max_attempts: 100,
+};
+
+/// How long a key stays locked after its nth failure, doubling from one
+/// second past the free attempts up to the full lockout.
+fn lock_seconds(failures: u64, limits: &Limits) -> Option<u64> {
+    if failures >= limits.max_attempts {
+        return Some(LOCKOUT_IN_SECONDS);
+    }
+    if failures <= limits.free_attempts {
+        return None;
+    }
+    let delay = 1u64
+        .checked_shl((failures - limits.free_attempts - 1) as u32)
+        .unwrap_or(u64::MAX);
+    Some(delay.min(LOCKOUT_IN_SECONDS))
+}
+
+// a client that waits the whole Retry-After should never still be locked
+fn round_up_to_seconds(remaining: Duration) -> u64 {
+    remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+}
+
+fn redis_connection(
+    redis_pool: &r2d2::Pool<redis::Client>,
+) -> Option<r2d2::PooledConnection<redis::Client>> {
+    redis_pool
+        .get_timeout(Duration::from_millis(REDIS_TIMEOUT_IN_MILLISECONDS))
+        .ok()
+}
+
+fn username_key(username: &str) -> String {
+    format!("user:{}", username)
+}
+
+fn ip_key(ip_address: &str) -> String {
+    format!("ip:{}", ip_address)
+}
+
+struct MemoryCounter {
+    failures: u64,
+    window_ends_at: Instant,
+    locked_until: Option<Instant>,
+}
+
+/// Failed login counters per username and per client address. Redis keeps
+/// them shared between instances, the in memory map only takes over when
+/// redis can not be reached.
+#[derive(Default)]
+pub struct LoginThrottle {
+    memory: Mutex<HashMap<String, MemoryCounter>>,
+}
+
+impl LoginThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seconds until a login for this username from this address is allowed
+    /// again, None when it can go ahead now.
+    pub fn retry_after(
+        &self,
+        redis_pool: &r2d2::Pool<redis::Client>,
+        username: &str,
+        ip_address: &str,
+    ) -> Option<u64> {
+        [username_key(username), ip_key(ip_address)]
+            .iter()
+            .filter_map(|key| self.locked_for(redis_pool, key))
+            .max()
+    }
+
+    /// Counts a failed login against both the username and the address.
+    /// Returns how long the username got locked by it, if at all.
+    pub fn record_failure(
+        &self,
+        redis_pool: &r2d2::Pool<redis::Client>,
+        username: &str,
+        ip_address: &str,
+    ) -> Option<u64> {
+        self.increment(redis_pool, &ip_key(ip_address), &IP_LIMITS);
+        self.increment(redis_pool, &username_key(username), &USERNAME_LIMITS)
+    }
+
+    /// A successful login starts the username over, the address keeps its count.
+    pub fn clear(&self, redis_pool: &r2d2::Pool<redis::Client>, username: &str) {
+        let key = username_key(username);
+
+        if let Some(mut redis_connection) = redis_connection(redis_pool) {
+            let _: Result<(), _> = redis_connection
+                .del(&[format!("login_fail:{}", key), format!("login_lock:{}", key)]);
+        }
+
+        if let Ok(mut memory) = self.memory.lock() {
+            memory.remove(&key);
+        }
+    }
+
+    /// Checks both stores, failures counted in memory while redis was down
+    /// still hold after it comes back.
+    fn locked_for(&self, redis_pool: &r2d2::Pool<redis::Client>, key: &str) -> Option<u64> {
+        let mut remaining = Duration::ZERO;
+
+        if let Some(mut redis_connection) = redis_connection(redis_pool) {
+            let ttl: Result<i64, _> = redis_connection.pttl(format!("login_lock:{}", key));
+            if let Ok(ttl) = ttl {
+                remaining = Duration::from_millis(ttl.max(0) as u64);
+            }
+        }
+
+        if let Ok(memory) = self.memory.lock() {
+            if let Some(locked_until) = memory.get(key).and_then(|counter| counter.locked_until) {
+                remaining = remaining.max(locked_until.saturating_duration_since(Instant::now()));
+            }
+        }
+
+        (!remaining.is_zero()).then(|| round_up_to_seconds(remaining))
+    }
+
+    fn increment(
+        &self,
+        redis_pool: &r2d2::Pool<redis::Client>,
+        key: &str,
+        limits: &Limits,
+    ) -> Option<u64> {
+        if let Some(mut redis_connection) = redis_connection(redis_pool) {
+            let failure_key = format!("login_fail:{}", key);
+            let failures: Result<u64, _> = redis_connection.incr(&failure_key, 1);
+            if let Ok(failures) = failures {
+                let _: Result<(), _> =
+                    redis_connection.expire(&failure_key, FAILURE_WINDOW_IN_SECONDS as i64);
+                let lock = lock_seconds(failures, limits);
+                if let Some(seconds) = lock {
+                    let _: Result<(), _> =
+                        redis_connection.set_ex(format!("login_lock:{}", key), 1, seconds);
+                }
+                return lock;
+            }
+        }
+
+        let mut memory = self.memory.lock().ok()?;
+        let now = Instant::now();
+
+        if memory.len() >= MAX_MEMORY_ENTRIES {
+            memory.retain(|_, counter| {
+                counter.window_ends_at > now
+                    || counter.locked_until.is_some_and(|until| until > now)
+            });
+        }
+
+        let counter = memory.entry(key.to_string()).or_insert(MemoryCounter {
+            failures: 0,
+            window_ends_at: now,
+            locked_until: None,
+        });
+        if counter.window_ends_at <= now {
+            counter.failures = 0;
+        }
+        counter.failures += 1;
+        counter.window_ends_at = now + Duration::from_secs(FAILURE_WINDOW_IN_SECONDS);
+
+        let lock = lock_seconds(counter.failures, limits);
+        if let Some(seconds) = lock {
+            counter.locked_until = Some(now + Duration::from_secs(seconds));
+        }
+        lock
+    }
+}
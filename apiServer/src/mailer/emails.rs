@@ -0,0 +1,50 @@
+use crate::{
+    dbcalls::email_tokens::{
+        create_email_token, EMAIL_VERIFICATION_EXPIRY_IN_SECONDS, PASSWORD_RESET_EXPIRY_IN_SECONDS,
+    },
+    models::email_token::EmailTokenPurpose,
+    AppState,
+};
+
+use super::mail::{send_email, OutgoingEmail};
+
+pub fn verification_email(app_url: &str, to: &str, token: &str) -> OutgoingEmail {
+    OutgoingEmail {
+        to: to.to_string(),
+        subject: "Verify your email".to_string(),
+        body: format!(
+            "Confirm this address by opening the link below, it expires in {} hours.\n\n{}/verify-email?token={}\n",
+            EMAIL_VERIFICATION_EXPIRY_IN_SECONDS / 3600,
+            app_url,
+            token
+        ),
+    }
+}
+
+pub fn password_reset_email(app_url: &str, to: &str, token: &str) -> OutgoingEmail {
+    OutgoingEmail {
+        to: to.to_string(),
+        subject: "Reset your password".to_string(),
+        body: format!(
+            "Someone asked to reset the password of your account. If it was you, open the link below, it expires in {} minutes.\n\n{}/reset-password?token={}\n\nIf it was not you, you can ignore this email.\n",
+            PASSWORD_RESET_EXPIRY_IN_SECONDS / 60,
+            app_url,
+            token
+        ),
+    }
+}
+
+/// Issues a fresh verification token for `email` and mails the link to it.
+pub async fn send_verification_email(
+    app_state: &AppState,
+    user_id: i32,
+    email: &str,
+) -> Result<(), String> {
+    let token =
+        create_email_token(app_state, user_id, email, EmailTokenPurpose::VerifyEmail).await?;
+    send_email(
+        app_state.mailer.clone(),
+        verification_email(&app_state.app_url, email, &token),
+    )
+    .await
+}
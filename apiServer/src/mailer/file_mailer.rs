@@ -0,0 +1,41 @@
+use std::{fs, path::PathBuf};
+
+use log::info;
+
+use crate::tokens::generate_random_token::generate_random_token;
+
+use super::mail::{Mailer, OutgoingEmail};
+
+/// Development mailer, every email is written to its own file and logged
+/// instead of leaving the machine.
+pub struct FileMailer {
+    directory: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+}
+
+impl Mailer for FileMailer {
+    fn send(&self, email: &OutgoingEmail) -> Result<(), String> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|_| "Issue creating the mail directory".to_string())?;
+
+        let file_name = format!(
+            "{}-{}.txt",
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            generate_random_token(8)
+        );
+        let path = self.directory.join(file_name);
+        let contents = format!(
+            "To: {}\nSubject: {}\n\n{}\n",
+            email.to, email.subject, email.body
+        );
+
+        fs::write(&path, contents).map_err(|_| "Issue writing the email".to_string())?;
+        info!("Email to {} written to {}", email.to, path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use actix_web::web;
+
+pub struct OutgoingEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Anything that can deliver an email, picked at startup from the `MAILER` env.
+/// Sending is blocking, go through `send_email` from async code.
+pub trait Mailer: Send + Sync {
+    fn send(&self, email: &OutgoingEmail) -> Result<(), String>;
+}
+
+/// Runs the send on actix's blocking pool so a slow smtp server does not stall a worker.
+pub async fn send_email(mailer: Arc<dyn Mailer>, email: OutgoingEmail) -> Result<(), String> {
+    match web::block(move || mailer.send(&email)).await {
+        Ok(send_result) => send_result,
+        Err(_) => Err("Issue sending the email".to_string()),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod emails;
+pub mod file_mailer;
+pub mod mail;
+pub mod smtp_mailer;
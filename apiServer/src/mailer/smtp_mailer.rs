@@ -0,0 +1,53 @@
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    Message, SmtpTransport, Transport,
+};
+
+use super::mail::{Mailer, OutgoingEmail};
+
+pub struct SmtpMailer {
+    transport: SmtpTransport,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(
+        host: &str,
+        port: u16,
+        username: String,
+        password: String,
+        from: &str,
+    ) -> Result<Self, String> {
+        let from = from
+            .parse::<Mailbox>()
+            .map_err(|_| "Invalid sender address".to_string())?;
+        let transport = SmtpTransport::starttls_relay(host)
+            .map_err(|_| "Issue configuring the smtp relay".to_string())?
+            .port(port)
+            .credentials(Credentials::new(username, password))
+            .build();
+        Ok(Self { transport, from })
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, email: &OutgoingEmail) -> Result<(), String> {
+        let to = email
+            .to
+            .parse::<Mailbox>()
+            .map_err(|_| "Invalid recipient address".to_string())?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&email.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(email.body.clone())
+            .map_err(|_| "Issue building the email".to_string())?;
+
+        self.transport
+            .send(&message)
+            .map(|_| ())
+            .map_err(|_| "Issue sending the email".to_string())
+    }
+}
@@ -3,23 +3,33 @@ use actix_web::{
     web, App, HttpServer,
 };
 use log::info;
+use login_guard::login_throttle::LoginThrottle;
+use mailer::{file_mailer::FileMailer, mail::Mailer, smtp_mailer::SmtpMailer};
 use redis::Client;
 use sqlx::{postgres::PgPoolOptions, Postgres};
-use std::env;
+use std::{env, path::PathBuf, sync::Arc};
 
 pub mod dbcalls;
+pub mod login_guard;
+pub mod mailer;
 pub mod middlewares;
 pub mod models;
 pub mod responses;
 pub mod routes;
 pub mod tokens;
 pub mod validators;
+pub mod webhook_delivery;
 
 pub struct AppState {
     pub database: sqlx::Pool<Postgres>,
     pub access_token_secret: String,
     pub redis_pool: r2d2::Pool<Client>,
     pub api_secret: String,
+    pub mailer: Arc<dyn Mailer>,
+    // base url of the web app, links in emails point there
+    pub app_url: String,
+    // shared by every worker so the in memory fallback counts all of them
+    pub login_throttle: Arc<LoginThrottle>,
 }
 
 #[actix_web::main]
@@ -33,6 +43,29 @@ async fn main() -> std::io::Result<()> {
     let access_token_secret =
         env::var("ACCESS_TOKEN_SECRET").expect("Issue finding the access token secret");
 
+    let app_url = env::var("APP_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
+
+    let mailer: Arc<dyn Mailer> = match env::var("MAILER").as_deref() {
+        Ok("smtp") => Arc::new(
+            SmtpMailer::new(
+                &env::var("SMTP_HOST").expect("Issue finding the smtp host"),
+                env::var("SMTP_PORT")
+                    .expect("Issue finding the smtp port")
+                    .parse()
+                    .expect("Issue parsing the smtp port"),
+                env::var("SMTP_USERNAME").expect("Issue finding the smtp username"),
+                env::var("SMTP_PASSWORD").expect("Issue finding the smtp password"),
+                &env::var("MAIL_FROM").expect("Issue finding the mail sender"),
+            )
+            .expect("Issue configuring the smtp mailer"),
+        ),
+        _ => Arc::new(FileMailer::new(PathBuf::from(
+            env::var("MAIL_OUTPUT_DIR").unwrap_or_else(|_| "./mail".to_string()),
+        ))),
+    };
+
+    let login_throttle = Arc::new(LoginThrottle::new());
+
     let redis_client =
         redis::Client::open("redis://127.0.0.1/").expect("Issue creating redis client");
 
@@ -47,6 +80,10 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Issue connecting to the database");
 
+    actix_web::rt::spawn(webhook_delivery::delivery_worker::run_delivery_worker(
+        pool.clone(),
+    ));
+
     info!("Starting Actix Web server...");
 
     HttpServer::new(move || {
@@ -57,6 +94,9 @@ async fn main() -> std::io::Result<()> {
                 access_token_secret: access_token_secret.clone(),
                 redis_pool: redis_pool.clone(),
                 api_secret: api_secret.clone(),
+                mailer: mailer.clone(),
+                app_url: app_url.clone(),
+                login_throttle: login_throttle.clone(),
             }))
             .route(
                 "/",
@@ -76,12 +116,101 @@ async fn main() -> std::io::Result<()> {
                         "/login",
                         web::post().to(routes::user::login_user::login_user),
                     )
+                    .route(
+                        "/login/twoFactor",
+                        web::post().to(routes::user::login_two_factor::login_two_factor),
+                    )
+                    .route(
+                        "/refresh",
+                        web::post().to(routes::user::refresh_access_token::refresh_access_token),
+                    )
+                    .route(
+                        "/verifyEmail",
+                        web::post().to(routes::user::verify_email::verify_email),
+                    )
+                    .route(
+                        "/forgotPassword",
+                        web::post().to(routes::user::forgot_password::forgot_password),
+                    )
+                    .route(
+                        "/resetPassword",
+                        web::post().to(routes::user::reset_password::reset_password),
+                    )
                     .service(
                         web::scope("/protected")
                             .wrap(from_fn(middlewares::auth_middleware::auth_middleware))
                             .route(
                                 "/currentUser",
                                 web::get().to(routes::user::current_user::get_current_user),
+                            )
+                            .route(
+                                "/delete",
+                                web::post().to(routes::user::delete_user::delete_user),
+                            )
+                            .route(
+                                "/logout",
+                                web::post().to(routes::user::logout_user::logout_user),
+                            )
+                            .route(
+                                "/changePassword",
+                                web::post().to(routes::user::change_password::change_password),
+                            )
+                            .route("/email", web::post().to(routes::user::set_email::set_email))
+                            .route(
+                                "/twoFactor/enroll",
+                                web::post().to(routes::user::enroll_two_factor::enroll_two_factor),
+                            )
+                            .route(
+                                "/twoFactor/confirm",
+                                web::post()
+                                    .to(routes::user::confirm_two_factor::confirm_two_factor),
+                            )
+                            .route(
+                                "/twoFactor/disable",
+                                web::post()
+                                    .to(routes::user::disable_two_factor::disable_two_factor),
+                            )
+                            .route(
+                                "/sessions",
+                                web::get().to(routes::user::list_sessions::list_sessions),
+                            )
+                            .route(
+                                "/sessions/revoke",
+                                web::post()
+                                    .to(routes::user::revoke_user_session::revoke_user_session),
+                            )
+                            .route("/bots", web::get().to(routes::user::list_bots::list_bots))
+                            .route(
+                                "/bots/create",
+                                web::post().to(routes::user::create_bot::create_bot),
+                            )
+                            .route(
+                                "/bots/regenerateToken",
+                                web::post()
+                                    .to(routes::user::regenerate_bot_token::regenerate_bot_token),
+                            )
+                            .route(
+                                "/bots/delete",
+                                web::post().to(routes::user::delete_bot::delete_bot),
+                            )
+                            .route(
+                                "/accessTokens",
+                                web::get().to(routes::user::list_access_tokens::list_access_tokens),
+                            )
+                            .route(
+                                "/accessTokens/create",
+                                web::post()
+                                    .to(routes::user::create_access_token::create_access_token),
+                            )
+                            .route(
+                                "/accessTokens/revoke",
+                                web::post()
+                                    .to(routes::user::revoke_access_token::revoke_access_token),
+                            )
+                            .route(
+                                "/sessions/revokeOthers",
+                                web::post()
+                                    .to(routes::user::revoke_other_sessions::revoke_other_sessions),
                             ),
                     ),
             )
@@ -97,9 +226,113 @@ async fn main() -> std::io::Result<()> {
                             "/addMember",
                             web::post()
                                 .to(routes::channel::add_user_to_channel::add_user_to_channel),
+                        )
+                        .route(
+                            "/direct",
+                            web::post()
+                                .to(routes::channel::create_direct_channel::create_direct_channel),
+                        )
+                        .route(
+                            "/memberRole",
+                            web::post().to(routes::channel::update_member_role::update_member_role),
+                        )
+                        .route(
+                            "/leave",
+                            web::post().to(routes::channel::leave_channel::leave_channel),
+                        )
+                        .route(
+                            "/removeMember",
+                            web::post().to(routes::channel::remove_member::remove_member),
+                        )
+                        .route(
+                            "/invite/create",
+                            web::post().to(routes::channel::create_invite::create_invite),
+                        )
+                        .route(
+                            "/invite/redeem",
+                            web::post().to(routes::channel::redeem_invite::redeem_invite),
+                        )
+                        .route(
+                            "/invite/revoke",
+                            web::post().to(routes::channel::revoke_invite::revoke_invite),
+                        )
+                        .route(
+                            "/invite/list/{channel_id}",
+                            web::get().to(routes::channel::list_invites::list_invites),
+                        )
+                        .route(
+                            "/webhook/incoming/create",
+                            web::post().to(
+                                routes::channel::create_incoming_webhook::create_incoming_webhook,
+                            ),
+                        )
+                        .route(
+                            "/webhook/incoming/revoke",
+                            web::post().to(
+                                routes::channel::revoke_incoming_webhook::revoke_incoming_webhook,
+                            ),
+                        )
+                        .route(
+                            "/webhook/incoming/list/{channel_id}",
+                            web::get().to(
+                                routes::channel::list_incoming_webhooks::list_incoming_webhooks,
+                            ),
+                        )
+                        .route(
+                            "/webhook/outgoing/create",
+                            web::post().to(
+                                routes::channel::create_outgoing_webhook::create_outgoing_webhook,
+                            ),
+                        )
+                        .route(
+                            "/webhook/outgoing/revoke",
+                            web::post().to(
+                                routes::channel::revoke_outgoing_webhook::revoke_outgoing_webhook,
+                            ),
+                        )
+                        .route(
+                            "/webhook/outgoing/list/{channel_id}",
+                            web::get().to(
+                                routes::channel::list_outgoing_webhooks::list_outgoing_webhooks,
+                            ),
+                        )
+                        .route(
+                            "/webhook/outgoing/deliveries/{webhook_id}",
+                            web::get().to(
+                                routes::channel::list_webhook_deliveries::list_webhook_deliveries,
+                            ),
+                        )
+                        .route(
+                            "/public",
+                            web::get()
+                                .to(routes::channel::list_public_channels::list_public_channels),
+                        )
+                        .route(
+                            "/join",
+                            web::post().to(routes::channel::join_channel::join_channel),
+                        )
+                        .route(
+                            "/update",
+                            web::post().to(routes::channel::update_channel::update_channel),
+                        )
+                        .route(
+                            "/transferOwnership",
+                            web::post().to(routes::channel::transfer_ownership::transfer_ownership),
+                        )
+                        .route(
+                            "/archive",
+                            web::post().to(routes::channel::archive_channel::archive_channel),
+                        )
+                        .route(
+                            "/delete",
+                            web::post().to(routes::channel::delete_channel::delete_channel),
                         ),
                 ),
             )
+            .service(web::scope("/api/v1/webhooks").route(
+                "/incoming/{token}",
+                web::post().to(routes::webhooks::post_incoming_webhook::post_incoming_webhook),
+            ))
             .service(
                 web::scope("/api/v1/message").service(
                     web::scope("/protected")
@@ -107,6 +340,31 @@ async fn main() -> std::io::Result<()> {
                         .route(
                             "/send",
                             web::post().to(routes::messages::send_message::send_message),
+                        )
+                        .route(
+                            "/edit",
+                            web::post().to(routes::messages::edit_message::edit_message),
+                        )
+                        .route(
+                            "/delete",
+                            web::post().to(routes::messages::delete_message::delete_message),
+                        )
+                        .route(
+                            "/thread/{message_id}",
+                            web::get().to(routes::messages::get_thread::get_thread),
+                        )
+                        .route(
+                            "/reaction/add",
+                            web::post().to(routes::messages::add_reaction::add_reaction),
+                        )
+                        .route(
+                            "/reaction/remove",
+                            web::post().to(routes::messages::remove_reaction::remove_reaction),
+                        )
+                        .route(
+                            "/{channel_id}",
+                            web::get()
+                                .to(routes::messages::get_message_history::get_message_history),
                         ),
                 ),
             )
@@ -114,6 +372,10 @@ async fn main() -> std::io::Result<()> {
                 "/websocket/isValidUser",
                 web::post().to(routes::user::current_user_for_socket::current_user_for_socket),
             )
+            .route(
+                "/websocket/isValidBot",
+                web::post().to(routes::user::bot_for_socket::bot_for_socket),
+            )
             .route(
                 "/websocket/channels",
                 web::post().to(routes::channel::get_user_channels::current_user_for_socket),
@@ -1,6 +1,7 @@
 use actix_web::{
     body::BoxBody,
     dev::{ServiceRequest, ServiceResponse},
+    http::{header, Method},
     middleware::Next,
     web::Data,
     Error, HttpMessage, HttpResponse,
@@ -8,27 +9,172 @@ use actix_web::{
 use redis::Commands;
 use serde::Serialize;
 
-use crate::{responses::general_error::GeneralError, AppState};
+use crate::{
+    dbcalls::{
+        access_tokens::{find_active_access_token, touch_access_token, ACCESS_TOKEN_PREFIX},
+        bots::{find_bot_by_token, BOT_SCOPES, BOT_TOKEN_PREFIX},
+    },
+    models::access_token::AccessTokenScope,
+    responses::general_error::GeneralError,
+    AppState,
+};
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct UserData {
     pub username: String,
     pub user_id: i32,
+    // None when the request came with a personal access token or a bot token
+    pub session_id: Option<String>,
 }
 
-pub async fn auth_middleware(
+/// Reads the access token from `Authorization: Bearer <jwt>`, falling back to the
+/// `accessToken` cookie browsers send. Other schemes, like a proxy's basic auth,
+/// are left alone.
+fn access_token_from_request(req: &ServiceRequest) -> Option<String> {
+    let bearer_token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim().to_string());
+    if bearer_token.is_some() {
+        return bearer_token.filter(|token| !token.is_empty());
+    }
+    req.cookie("accessToken")
+        .map(|cookie| cookie.value().to_string())
+}
+
+// channel routes that can take the channel away from its members or send its
+// messages somewhere else, a leaked token must not be enough for them
+const SESSION_ONLY_CHANNEL_PATHS: [&str; 4] = [
+    "/api/v1/channel/protected/memberRole",
+    "/api/v1/channel/protected/transferOwnership",
+    "/api/v1/channel/protected/archive",
+    "/api/v1/channel/protected/delete",
+];
+
+/// The scope a personal access token or bot token needs for a request, None
+/// where only a login session will do, like managing the account or its tokens.
+fn required_scope(req: &ServiceRequest) -> Option<AccessTokenScope> {
+    let read = req.method() == Method::GET;
+    let path = req.path();
+
+    if SESSION_ONLY_CHANNEL_PATHS.contains(&path)
+        || path.starts_with("/api/v1/channel/protected/webhook/")
+    {
+        return None;
+    }
+    if path.starts_with("/api/v1/channel/") {
+        return Some(if read {
+            AccessTokenScope::ChannelsRead
+        } else {
+            AccessTokenScope::ChannelsWrite
+        });
+    }
+    if path.starts_with("/api/v1/message/") {
+        return Some(if read {
+            AccessTokenScope::MessagesRead
+        } else {
+            AccessTokenScope::MessagesWrite
+        });
+    }
+    if read && path == "/api/v1/user/protected/currentUser" {
+        return Some(AccessTokenScope::UserRead);
+    }
+    None
+}
+
+/// Who a personal access token or bot token acts for, and whether it may make
+/// the request at hand.
+struct TokenGrant {
+    user_id: i32,
+    username: String,
+    allowed: bool,
+}
+
+async fn token_grant(
+    state: &AppState,
+    token: &str,
+    required_scope: AccessTokenScope,
+) -> Result<Option<TokenGrant>, String> {
+    if token.starts_with(BOT_TOKEN_PREFIX) {
+        let bot = find_bot_by_token(state, token).await?;
+        return Ok(bot.map(|bot| TokenGrant {
+            user_id: bot.id,
+            username: bot.username,
+            allowed: BOT_SCOPES.contains(&required_scope),
+        }));
+    }
+
+    let access_token = find_active_access_token(state, token).await?;
+    if let Some(access_token) = &access_token {
+        touch_access_token(state, access_token.id).await;
+    }
+    Ok(access_token.map(|access_token| TokenGrant {
+        allowed: access_token.allows(required_scope),
+        user_id: access_token.user_id,
+        username: access_token.username,
+    }))
+}
+
+async fn token_auth(
     req: ServiceRequest,
     next: Next<BoxBody>,
-) -> Result<ServiceResponse<impl actix_web::body::MessageBody>, Error> {
-    if req.cookie("accessToken").is_none() {
+    state: Data<AppState>,
+    token: &str,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let required_scope = required_scope(&req);
+    if required_scope.is_none() {
+        let error_response = HttpResponse::Forbidden().json(GeneralError {
+            message: "Tokens can not be used here, login instead".to_string(),
+        });
+        return Ok(req.into_response(error_response.map_into_boxed_body()));
+    }
+
+    let required_scope = required_scope.unwrap();
+    let grant = token_grant(&state, token, required_scope).await;
+
+    if let Err(err_string) = grant {
+        let error_response = HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+        return Ok(req.into_response(error_response.map_into_boxed_body()));
+    }
+
+    if grant.as_ref().unwrap().is_none() {
         let error_response = HttpResponse::Unauthorized().json(GeneralError {
-            message: "Unauthorized: Missing accessToken cookie".to_string(),
+            message: "Invalid token".to_string(),
         });
         return Ok(req.into_response(error_response.map_into_boxed_body()));
     }
-    if req.cookie("userId").is_none() {
+
+    let grant = grant.unwrap().unwrap();
+
+    if !grant.allowed {
+        let error_response = HttpResponse::Forbidden().json(GeneralError {
+            message: format!("This token needs the {} scope", required_scope.as_str()),
+        });
+        return Ok(req.into_response(error_response.map_into_boxed_body()));
+    }
+
+    req.extensions_mut().insert(UserData {
+        user_id: grant.user_id,
+        username: grant.username,
+        session_id: None,
+    });
+
+    next.call(req).await
+}
+
+pub async fn auth_middleware(
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+) -> Result<ServiceResponse<impl actix_web::body::MessageBody>, Error> {
+    let access_token = access_token_from_request(&req);
+    if access_token.is_none() {
         let error_response = HttpResponse::Unauthorized().json(GeneralError {
-            message: "Unauthorized: Missing userId cookie".to_string(),
+            message: "Unauthorized: Missing access token".to_string(),
         });
         return Ok(req.into_response(error_response.map_into_boxed_body()));
     }
@@ -43,14 +189,19 @@ pub async fn auth_middleware(
         }
     };
 
-    let token = req.cookie("accessToken").unwrap().value().to_string();
-    let user_id = req.cookie("userId").unwrap().value().to_string();
+    let token = access_token.unwrap();
+    if token.starts_with(ACCESS_TOKEN_PREFIX) || token.starts_with(BOT_TOKEN_PREFIX) {
+        let state = state.clone();
+        return token_auth(req, next, state, &token).await;
+    }
+
+    // the user id always comes from the signed claims, never from a separate cookie
     let token_eval_result =
         crate::tokens::validate_token::validate_token(&token, &state.access_token_secret);
 
-    if token_eval_result.is_err() {
+    if let Err(err_string) = token_eval_result {
         let error_response = HttpResponse::Unauthorized().json(GeneralError {
-            message: token_eval_result.unwrap_err(),
+            message: err_string,
         });
         return Ok(req.into_response(error_response.map_into_boxed_body()));
     }
@@ -59,14 +210,16 @@ pub async fn auth_middleware(
     let redis_connection_result = state.redis_pool.get();
 
     // use redis to authenticate
-    if redis_connection_result.is_ok() {
-        let key = format!("auth:{}", user_id);
-        let token_redis_result: Result<String, _> = redis_connection_result.unwrap().get(key);
+    if let Ok(mut redis_connection) = redis_connection_result {
+        let key = crate::dbcalls::sessions::session_redis_key(claims.user_id, &claims.session_id);
+        let token_redis_result: Result<String, _> = redis_connection.get(key);
         if let Ok(token_redis) = token_redis_result {
             if token_redis == token {
+                crate::dbcalls::sessions::touch_session(state, &claims.session_id).await;
                 req.extensions_mut().insert(UserData {
                     user_id: claims.user_id,
                     username: claims.username,
+                    session_id: Some(claims.session_id),
                 });
                 return next.call(req).await;
             } else {
@@ -79,24 +232,26 @@ pub async fn auth_middleware(
     }
 
     // redis is not connected so use this alternate way
-    let user_exists = crate::dbcalls::check_user_exists::check_user_exists(
+    let session_active = crate::dbcalls::check_session_active::check_session_active(
         claims.user_id,
-        &claims.username,
+        &claims.session_id,
         state,
     )
     .await;
 
-    match user_exists {
+    match session_active {
         Err(err_string) => {
-            let error_response = HttpResponse::BadRequest().json(GeneralError {
+            let error_response = HttpResponse::Unauthorized().json(GeneralError {
                 message: err_string,
             });
             Ok(req.into_response(error_response.map_into_boxed_body()))
         }
         Ok(_) => {
+            crate::dbcalls::sessions::touch_session(state, &claims.session_id).await;
             req.extensions_mut().insert(UserData {
                 user_id: claims.user_id,
                 username: claims.username,
+                session_id: Some(claims.session_id),
             });
 
             next.call(req).await
@@ -0,0 +1,52 @@
+use sqlx::prelude::FromRow;
+
+/// What a personal access token may be used for. Reads are GET requests,
+/// everything else is a write.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessTokenScope {
+    ChannelsRead,
+    ChannelsWrite,
+    MessagesRead,
+    MessagesWrite,
+    UserRead,
+}
+
+impl AccessTokenScope {
+    pub const ALL: [AccessTokenScope; 5] = [
+        AccessTokenScope::ChannelsRead,
+        AccessTokenScope::ChannelsWrite,
+        AccessTokenScope::MessagesRead,
+        AccessTokenScope::MessagesWrite,
+        AccessTokenScope::UserRead,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessTokenScope::ChannelsRead => "channels:read",
+            AccessTokenScope::ChannelsWrite => "channels:write",
+            AccessTokenScope::MessagesRead => "messages:read",
+            AccessTokenScope::MessagesWrite => "messages:write",
+            AccessTokenScope::UserRead => "user:read",
+        }
+    }
+
+    pub fn parse(scope: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|known_scope| known_scope.as_str() == scope)
+    }
+}
+
+#[derive(FromRow, serde::Serialize)]
+pub struct AccessTokenDb {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -5,4 +5,17 @@ pub struct ChannelDB {
     pub id: i32,
     pub name: String,
     pub admin_id: i32,
+    pub is_direct: bool,
+    pub direct_key: Option<String>,
+    pub is_public: bool,
+    pub topic: Option<String>,
+    pub description: Option<String>,
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(FromRow, serde::Serialize)]
+pub struct PublicChannel {
+    pub id: i32,
+    pub name: String,
+    pub member_count: i64,
 }
@@ -0,0 +1,14 @@
+use sqlx::prelude::FromRow;
+
+#[derive(FromRow, serde::Serialize)]
+pub struct ChannelInviteDb {
+    pub id: i32,
+    pub code: String,
+    pub channel_id: i32,
+    pub created_by: i32,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,21 @@
+use sqlx::prelude::FromRow;
+
+#[derive(sqlx::Type, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[sqlx(type_name = "email_token_purpose", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EmailTokenPurpose {
+    VerifyEmail,
+    ResetPassword,
+}
+
+#[derive(FromRow)]
+pub struct EmailTokenDb {
+    pub id: i32,
+    pub user_id: i32,
+    pub purpose: EmailTokenPurpose,
+    pub email: String,
+    pub token_hash: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,15 @@
+use sqlx::prelude::FromRow;
+
+#[derive(FromRow, serde::Serialize)]
+pub struct IncomingWebhookDb {
+    pub id: i32,
+    pub channel_id: i32,
+    pub integration_user_id: i32,
+    pub name: String,
+    pub created_by: i32,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,7 @@
+#[derive(sqlx::Type, Clone, Copy, PartialEq, Eq, Debug)]
+#[sqlx(type_name = "login_failure_reason", rename_all = "snake_case")]
+pub enum LoginFailureReason {
+    UnknownUser,
+    WrongPassword,
+    WrongTwoFactorCode,
+}
@@ -1,7 +1,33 @@
 use sqlx::prelude::FromRow;
 
+#[derive(sqlx::Type, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[sqlx(type_name = "channel_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelRole {
+    Owner,
+    Admin,
+    Moderator,
+    Member,
+    ReadOnly,
+}
+
+impl ChannelRole {
+    /// Higher rank means more authority in the channel.
+    pub fn rank(&self) -> u8 {
+        match self {
+            ChannelRole::Owner => 4,
+            ChannelRole::Admin => 3,
+            ChannelRole::Moderator => 2,
+            ChannelRole::Member => 1,
+            ChannelRole::ReadOnly => 0,
+        }
+    }
+}
+
 #[derive(FromRow, serde::Serialize)]
 pub struct MembershipDb {
     pub user_id: i32,
     pub channel_id: i32,
+    pub role: ChannelRole,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
 }
@@ -5,4 +5,35 @@ pub struct MessagesDb {
     pub id: i32,
     pub sender_id: i32,
     pub channel_id: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(FromRow, serde::Serialize)]
+pub struct MessageWithSender {
+    pub id: i32,
+    pub sender_id: i32,
+    pub username: String,
+    pub message: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub edited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub parent_id: Option<i32>,
+    pub reply_count: i64,
+    pub reactions: sqlx::types::Json<Vec<ReactionCount>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: i64,
+}
+
+#[derive(FromRow, serde::Serialize)]
+pub struct MessageContentDb {
+    pub id: i32,
+    pub sender_id: i32,
+    pub channel_id: i32,
+    pub message: String,
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub parent_id: Option<i32>,
 }
@@ -1,4 +1,14 @@
+pub mod access_token;
 pub mod channel;
+pub mod channel_invite;
+pub mod email_token;
+pub mod incoming_webhook;
+pub mod login_attempt;
 pub mod membership;
 pub mod message;
+pub mod outgoing_webhook;
+pub mod published_events;
+pub mod refresh_token;
+pub mod session;
+pub mod two_factor_challenge;
 pub mod user;
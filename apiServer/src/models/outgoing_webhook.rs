@@ -0,0 +1,37 @@
+use sqlx::prelude::FromRow;
+
+#[derive(FromRow, serde::Serialize)]
+pub struct OutgoingWebhookDb {
+    pub id: i32,
+    pub channel_id: i32,
+    pub created_by: i32,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::Type, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(FromRow, serde::Serialize)]
+pub struct WebhookDeliveryDb {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: sqlx::types::JsonValue,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,206 @@
+// payloads published on a channel's redis topic. The websocket server forwards
+// them as they are and routes on `event` and `user_id`, so each one is built
+// here and nowhere else.
+
+#[derive(serde::Serialize)]
+pub struct PublishedMessage {
+    message_id: i32,
+    message: String,
+    sender: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<i32>,
+}
+
+impl PublishedMessage {
+    pub fn new(
+        message_id: i32,
+        message: String,
+        sender: i32,
+        created_at: chrono::DateTime<chrono::Utc>,
+        parent_id: Option<i32>,
+    ) -> Self {
+        Self {
+            message_id,
+            message,
+            sender,
+            created_at,
+            parent_id,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedEdit {
+    event: &'static str,
+    message_id: i32,
+    message: String,
+    sender: i32,
+    edited_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PublishedEdit {
+    pub fn new(
+        message_id: i32,
+        message: String,
+        sender: i32,
+        edited_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            event: "edited",
+            message_id,
+            message,
+            sender,
+            edited_at,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedDelete {
+    event: &'static str,
+    message_id: i32,
+    deleted_by: i32,
+}
+
+impl PublishedDelete {
+    pub fn new(message_id: i32, deleted_by: i32) -> Self {
+        Self {
+            event: "deleted",
+            message_id,
+            deleted_by,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedReaction {
+    event: &'static str,
+    message_id: i32,
+    emoji: String,
+    sender: i32,
+}
+
+impl PublishedReaction {
+    pub fn added(message_id: i32, emoji: String, sender: i32) -> Self {
+        Self {
+            event: "reaction_added",
+            message_id,
+            emoji,
+            sender,
+        }
+    }
+
+    pub fn removed(message_id: i32, emoji: String, sender: i32) -> Self {
+        Self {
+            event: "reaction_removed",
+            message_id,
+            emoji,
+            sender,
+        }
+    }
+}
+
+/// Tells the websocket server to stop sending the channel to `user_id`, after
+/// they got this event.
+#[derive(serde::Serialize)]
+pub struct PublishedMemberRemoved {
+    event: &'static str,
+    user_id: i32,
+    sender: i32,
+}
+
+impl PublishedMemberRemoved {
+    pub fn new(user_id: i32, sender: i32) -> Self {
+        Self {
+            event: "member_removed",
+            user_id,
+            sender,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedChannelUpdate {
+    event: &'static str,
+    sender: i32,
+    channel_id: i32,
+    name: String,
+    topic: Option<String>,
+    description: Option<String>,
+}
+
+impl PublishedChannelUpdate {
+    pub fn new(
+        sender: i32,
+        channel_id: i32,
+        name: String,
+        topic: Option<String>,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            event: "channel_updated",
+            sender,
+            channel_id,
+            name,
+            topic,
+            description,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedChannelArchived {
+    event: &'static str,
+    sender: i32,
+    channel_id: i32,
+    archived: bool,
+}
+
+impl PublishedChannelArchived {
+    pub fn new(sender: i32, channel_id: i32, archived: bool) -> Self {
+        Self {
+            event: "channel_archived",
+            sender,
+            channel_id,
+            archived,
+        }
+    }
+}
+
+/// Tells the websocket server to drop every subscriber of the channel.
+#[derive(serde::Serialize)]
+pub struct PublishedChannelDeleted {
+    event: &'static str,
+    sender: i32,
+    channel_id: i32,
+}
+
+impl PublishedChannelDeleted {
+    pub fn new(sender: i32, channel_id: i32) -> Self {
+        Self {
+            event: "channel_deleted",
+            sender,
+            channel_id,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PublishedOwnershipTransferred {
+    event: &'static str,
+    sender: i32,
+    channel_id: i32,
+    user_id: i32,
+}
+
+impl PublishedOwnershipTransferred {
+    pub fn new(sender: i32, channel_id: i32, user_id: i32) -> Self {
+        Self {
+            event: "ownership_transferred",
+            sender,
+            channel_id,
+            user_id,
+        }
+    }
+}
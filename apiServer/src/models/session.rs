@@ -0,0 +1,13 @@
+use sqlx::prelude::FromRow;
+
+#[derive(FromRow, serde::Serialize)]
+pub struct SessionDb {
+    pub id: String,
+    pub user_id: i32,
+    pub device_label: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
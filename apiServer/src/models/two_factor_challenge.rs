@@ -0,0 +1,12 @@
+use sqlx::prelude::FromRow;
+
+#[derive(FromRow)]
+pub struct TwoFactorChallengeDb {
+    pub id: i32,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub device_label: Option<String>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
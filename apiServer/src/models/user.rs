@@ -1,9 +1,23 @@
 use sqlx::prelude::FromRow;
 
+#[derive(sqlx::Type, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[sqlx(type_name = "user_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UserKind {
+    Human,
+    Bot,
+    Integration,
+}
+
 #[derive(FromRow, serde::Serialize)]
 pub struct UserFromDB {
     pub id: i32,
     pub username: String,
+    pub email: Option<String>,
+    pub email_verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub totp_enabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub kind: UserKind,
+    pub bot_owner_id: Option<i32>,
 }
 
 #[derive(FromRow, serde::Serialize)]
@@ -11,4 +25,5 @@ pub struct UserFromDBWithPassword {
     pub id: i32,
     pub username: String,
     pub password: String,
+    pub totp_enabled_at: Option<chrono::DateTime<chrono::Utc>>,
 }
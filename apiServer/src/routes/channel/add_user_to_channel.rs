@@ -2,6 +2,9 @@ use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
 use validator::Validate;
 
 use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
     middlewares::auth_middleware::UserData,
     models::{channel::ChannelDB, membership::MembershipDb, user::UserFromDB},
     validators::add_user_to_channel_type::AddUserToChannel,
@@ -35,8 +38,7 @@ pub async fn add_user_to_channel(
         );
     }
 
-    let extensions = req.extensions();
-    let user_data = extensions.get::<UserData>().unwrap();
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
 
     if add_user_to_channel_data.0.username == user_data.username {
         return HttpResponse::Unauthorized().json(crate::responses::general_error::GeneralError {
@@ -44,10 +46,11 @@ pub async fn add_user_to_channel(
         });
     }
 
-    let channel_result = sqlx::query_as::<_, ChannelDB>("select * from channel where name=$1")
-        .bind(&add_user_to_channel_data.0.channel_name)
-        .fetch_optional(&app_state.database)
-        .await;
+    let channel_result =
+        sqlx::query_as::<_, ChannelDB>("select * from channel where name=$1 and not is_direct")
+            .bind(&add_user_to_channel_data.0.channel_name)
+            .fetch_optional(&app_state.database)
+            .await;
 
     if channel_result.is_err() {
         return HttpResponse::InternalServerError().json(
@@ -63,16 +66,24 @@ pub async fn add_user_to_channel(
         });
     }
 
-    if channel_result.as_ref().unwrap().as_ref().unwrap().admin_id != user_data.user_id {
-        return HttpResponse::Unauthorized().json(crate::responses::general_error::GeneralError {
-            message: "You are not the channel admin".to_string(),
-        });
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        channel_result.as_ref().unwrap().as_ref().unwrap().id,
+        ChannelPermission::AddMembers,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
     }
 
-    let user_result = sqlx::query_as::<_, UserFromDB>("select * from users where username=$1")
-        .bind(add_user_to_channel_data.0.username)
-        .fetch_optional(&app_state.database)
-        .await;
+    let user_result = sqlx::query_as::<_, UserFromDB>(
+        "select * from users where username=$1 and kind <> 'integration' and deleted_at is null",
+    )
+    .bind(add_user_to_channel_data.0.username)
+    .fetch_optional(&app_state.database)
+    .await;
 
     if user_result.is_err() {
         return HttpResponse::InternalServerError().json(
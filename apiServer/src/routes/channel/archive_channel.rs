@@ -0,0 +1,69 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{channel::ChannelDB, published_events::PublishedChannelArchived},
+    responses::general_error::GeneralError,
+    validators::archive_channel_type::ArchiveChannel,
+    AppState,
+};
+
+pub async fn archive_channel(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    archive_channel_data: web::Json<ArchiveChannel>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        archive_channel_data.0.channel_id,
+        ChannelPermission::ArchiveChannel,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let updated_channel_result = sqlx::query_as::<_, ChannelDB>(
+        "update channel set archived_at = case when $1 then coalesce(archived_at, now()) end
+        where id = $2 returning *",
+    )
+    .bind(archive_channel_data.0.archived)
+    .bind(archive_channel_data.0.channel_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if updated_channel_result.is_err() || updated_channel_result.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue archiving the channel".to_string(),
+        });
+    }
+
+    let updated_channel = updated_channel_result.unwrap().unwrap();
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_channel_archived = PublishedChannelArchived::new(
+        user_data.user_id,
+        updated_channel.id,
+        updated_channel.archived_at.is_some(),
+    );
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_channel_archived).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(updated_channel.id, json_message);
+    }
+
+    HttpResponse::Ok().json(updated_channel)
+}
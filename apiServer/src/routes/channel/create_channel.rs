@@ -31,13 +31,13 @@ pub async fn create_channel(
         );
     }
 
-    let extensions = req.extensions();
-    let user_data = extensions.get::<UserData>().unwrap();
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
 
     let channel_name = create_channel_data.0.channel_name;
+    let is_public = create_channel_data.0.is_public;
 
     let existing_channel_with_same_name =
-        sqlx::query_as::<_, ChannelDB>("select * from channel where name = $1")
+        sqlx::query_as::<_, ChannelDB>("select * from channel where name = $1 and not is_direct")
             .bind(&channel_name)
             .fetch_optional(&app_state.database)
             .await;
@@ -66,10 +66,11 @@ pub async fn create_channel(
     let mut transaction = transaction_res.unwrap();
 
     let create_channel_result = sqlx::query_as::<_, ChannelDB>(
-        "INSERT INTO channel (name, admin_id) VALUES ($1, $2) returning *",
+        "INSERT INTO channel (name, admin_id, is_public) VALUES ($1, $2, $3) returning *",
     )
     .bind(channel_name)
     .bind(user_data.user_id)
+    .bind(is_public)
     .fetch_optional(transaction.as_mut())
     .await;
 
@@ -92,7 +93,7 @@ pub async fn create_channel(
     }
 
     let add_user_to_channel_result =
-        sqlx::query("INSERT INTO membership (user_id, channel_id) VALUES ($1, $2)")
+        sqlx::query("INSERT INTO membership (user_id, channel_id, role) VALUES ($1, $2, 'owner')")
             .bind(user_data.user_id)
             .bind(create_channel_result.as_ref().unwrap().as_ref().unwrap().id)
             .execute(transaction.as_mut())
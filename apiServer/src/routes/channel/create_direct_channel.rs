@@ -0,0 +1,171 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    middlewares::auth_middleware::UserData,
+    models::{channel::ChannelDB, user::UserFromDB},
+    responses::general_error::GeneralError,
+    validators::direct_message_type::DirectMessageType,
+    AppState,
+};
+
+pub async fn create_direct_channel(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    direct_message_data: web::Json<DirectMessageType>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = direct_message_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if direct_message_data.0.username == user_data.username {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Can not start a conversation with yourself".to_string(),
+        });
+    }
+
+    let user_result = sqlx::query_as::<_, UserFromDB>(
+        "select * from users where username=$1 and kind <> 'integration' and deleted_at is null",
+    )
+    .bind(&direct_message_data.0.username)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if user_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if user_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let other_user = user_result.unwrap().unwrap();
+
+    // the same pair of users always maps to the same key, whoever starts the conversation
+    let direct_key = format!(
+        "{}:{}",
+        user_data.user_id.min(other_user.id),
+        user_data.user_id.max(other_user.id)
+    );
+
+    let existing_channel_result =
+        sqlx::query_as::<_, ChannelDB>("select * from channel where direct_key = $1")
+            .bind(&direct_key)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if existing_channel_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Some(existing_channel) = existing_channel_result.unwrap() {
+        return HttpResponse::Ok().json(existing_channel);
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    // a concurrent request for the same pair loses the race on direct_key and reads the winner
+    let create_channel_result = sqlx::query_as::<_, ChannelDB>(
+        "INSERT INTO channel (name, admin_id, is_direct, direct_key) VALUES ('direct', $1, true, $2)
+        ON CONFLICT (direct_key) DO NOTHING returning *",
+    )
+    .bind(user_data.user_id)
+    .bind(&direct_key)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if create_channel_result.is_err() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue rolling back the transaction".to_string(),
+            });
+        }
+
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue creating the conversation".to_string(),
+        });
+    }
+
+    let created_channel = match create_channel_result.unwrap() {
+        Some(channel) => channel,
+        None => {
+            let _ = transaction.rollback().await;
+            let existing_channel_result =
+                sqlx::query_as::<_, ChannelDB>("select * from channel where direct_key = $1")
+                    .bind(&direct_key)
+                    .fetch_one(&app_state.database)
+                    .await;
+
+            return match existing_channel_result {
+                Ok(existing_channel) => HttpResponse::Ok().json(existing_channel),
+                Err(_) => HttpResponse::InternalServerError().json(GeneralError {
+                    message: "Issue talking to the database".to_string(),
+                }),
+            };
+        }
+    };
+
+    let add_users_to_channel_result =
+        sqlx::query("INSERT INTO membership (user_id, channel_id) VALUES ($1, $3), ($2, $3)")
+            .bind(user_data.user_id)
+            .bind(other_user.id)
+            .bind(created_channel.id)
+            .execute(transaction.as_mut())
+            .await;
+
+    if add_users_to_channel_result.is_err() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue rolling back the transaction".to_string(),
+            });
+        }
+
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue adding users to the conversation".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(created_channel)
+}
@@ -0,0 +1,102 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::{
+        channel_permission::{
+            check_channel_permission, permission_error_response, ChannelPermission,
+        },
+        incoming_webhooks::create_incoming_webhook as insert_incoming_webhook,
+    },
+    middlewares::auth_middleware::UserData,
+    models::incoming_webhook::IncomingWebhookDb,
+    responses::general_error::GeneralError,
+    validators::create_incoming_webhook_type::CreateIncomingWebhook,
+    AppState,
+};
+
+#[derive(serde::Serialize)]
+struct IncomingWebhookWithUrl {
+    #[serde(flatten)]
+    webhook: IncomingWebhookDb,
+    url: String,
+}
+
+pub async fn create_incoming_webhook(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    create_webhook_data: web::Json<CreateIncomingWebhook>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = create_webhook_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        create_webhook_data.0.channel_id,
+        ChannelPermission::ManageWebhooks,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let new_webhook = insert_incoming_webhook(
+        transaction.as_mut(),
+        create_webhook_data.0.channel_id,
+        user_data.user_id,
+        &create_webhook_data.0.name,
+    )
+    .await;
+
+    if new_webhook.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue creating the webhook".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    let (webhook, token) = new_webhook.unwrap();
+
+    // the url carries the secret, it is never shown again
+    HttpResponse::Ok().json(IncomingWebhookWithUrl {
+        webhook,
+        url: format!("{}/api/v1/webhooks/incoming/{}", app_state.app_url, token),
+    })
+}
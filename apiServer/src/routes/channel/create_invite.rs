@@ -0,0 +1,80 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::channel_invite::ChannelInviteDb,
+    responses::general_error::GeneralError,
+    validators::create_invite_type::CreateInvite,
+    AppState,
+};
+
+pub async fn create_invite(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    create_invite_data: web::Json<CreateInvite>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = create_invite_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        create_invite_data.0.channel_id,
+        ChannelPermission::ManageInvites,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let expires_at = create_invite_data
+        .0
+        .expires_in_seconds
+        .map(|seconds| chrono::Utc::now() + chrono::Duration::seconds(seconds));
+
+    let code = crate::tokens::generate_random_token::generate_random_token(12);
+
+    let new_invite = sqlx::query_as::<_, ChannelInviteDb>(
+        "insert into channel_invites(code, channel_id, created_by, expires_at, max_uses)
+        values ($1, $2, $3, $4, $5) returning *",
+    )
+    .bind(code)
+    .bind(create_invite_data.0.channel_id)
+    .bind(user_data.user_id)
+    .bind(expires_at)
+    .bind(create_invite_data.0.max_uses)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if new_invite.is_err() || new_invite.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue creating the invite".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(new_invite.unwrap().unwrap())
+}
@@ -0,0 +1,96 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::{
+        channel_permission::{
+            check_channel_permission, permission_error_response, ChannelPermission,
+        },
+        outgoing_webhooks::generate_webhook_secret,
+    },
+    middlewares::auth_middleware::UserData,
+    models::outgoing_webhook::OutgoingWebhookDb,
+    responses::general_error::GeneralError,
+    validators::create_outgoing_webhook_type::CreateOutgoingWebhook,
+    AppState,
+};
+
+#[derive(serde::Serialize)]
+struct OutgoingWebhookWithSecret {
+    #[serde(flatten)]
+    webhook: OutgoingWebhookDb,
+    secret: String,
+}
+
+pub async fn create_outgoing_webhook(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    create_webhook_data: web::Json<CreateOutgoingWebhook>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = create_webhook_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    if !create_webhook_data.0.url.starts_with("http://")
+        && !create_webhook_data.0.url.starts_with("https://")
+    {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Webhook url should use http or https".to_string(),
+        });
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        create_webhook_data.0.channel_id,
+        ChannelPermission::ManageWebhooks,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let secret = generate_webhook_secret();
+
+    let new_webhook = sqlx::query_as::<_, OutgoingWebhookDb>(
+        "insert into outgoing_webhooks(channel_id, created_by, url, secret)
+        values ($1, $2, $3, $4) returning *",
+    )
+    .bind(create_webhook_data.0.channel_id)
+    .bind(user_data.user_id)
+    .bind(&create_webhook_data.0.url)
+    .bind(&secret)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if new_webhook.is_err() || new_webhook.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue creating the webhook".to_string(),
+        });
+    }
+
+    // receivers check signatures with this secret, it is never shown again
+    HttpResponse::Ok().json(OutgoingWebhookWithSecret {
+        webhook: new_webhook.unwrap().unwrap(),
+        secret,
+    })
+}
@@ -0,0 +1,61 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::published_events::PublishedChannelDeleted,
+    responses::general_error::GeneralError,
+    validators::delete_channel_type::DeleteChannel,
+    AppState,
+};
+
+pub async fn delete_channel(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    delete_channel_data: web::Json<DeleteChannel>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+    let channel_id = delete_channel_data.0.channel_id;
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        channel_id,
+        ChannelPermission::DeleteChannel,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    // memberships, invites and messages go with it through the cascading foreign keys
+    let delete_result = sqlx::query("delete from channel where id = $1")
+        .bind(channel_id)
+        .execute(&app_state.database)
+        .await;
+
+    if delete_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue deleting the channel".to_string(),
+        });
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_channel_deleted = PublishedChannelDeleted::new(user_data.user_id, channel_id);
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_channel_deleted).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(channel_id, json_message);
+    }
+
+    HttpResponse::Ok().json("channel deleted")
+}
@@ -0,0 +1,67 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    middlewares::auth_middleware::UserData,
+    models::{channel::ChannelDB, membership::MembershipDb},
+    responses::general_error::GeneralError,
+    validators::join_channel_type::JoinChannel,
+    AppState,
+};
+
+pub async fn join_channel(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    join_channel_data: web::Json<JoinChannel>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let channel_result = sqlx::query_as::<_, ChannelDB>("select * from channel where id=$1")
+        .bind(join_channel_data.0.channel_id)
+        .fetch_optional(&app_state.database)
+        .await;
+
+    if channel_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    // private and archived channels are reported as missing so they can not be discovered by id
+    let channel = channel_result.unwrap();
+    if channel.is_none()
+        || !channel.as_ref().unwrap().is_public
+        || channel.as_ref().unwrap().archived_at.is_some()
+    {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Channel not found".to_string(),
+        });
+    }
+
+    let new_member = sqlx::query_as::<_, MembershipDb>(
+        "insert into membership(user_id, channel_id) values ($1,$2)
+        on conflict do nothing returning *",
+    )
+    .bind(user_data.user_id)
+    .bind(channel.unwrap().id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if new_member.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue inserting to the database".to_string(),
+        });
+    }
+
+    if new_member.as_ref().unwrap().is_none() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Already part of this channel".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(new_member.unwrap().unwrap())
+}
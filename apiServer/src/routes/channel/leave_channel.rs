@@ -0,0 +1,86 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission, PermissionCheck,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{
+        channel::ChannelDB, membership::ChannelRole, published_events::PublishedMemberRemoved,
+    },
+    responses::general_error::GeneralError,
+    validators::leave_channel_type::LeaveChannel,
+    AppState,
+};
+
+pub async fn leave_channel(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    leave_channel_data: web::Json<LeaveChannel>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        leave_channel_data.0.channel_id,
+        ChannelPermission::ReadMessages,
+    )
+    .await;
+
+    let membership = match permission_result {
+        Ok(PermissionCheck::Allowed(membership)) => membership,
+        _ => return permission_error_response(&permission_result).unwrap(),
+    };
+
+    if membership.role == ChannelRole::Owner {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Transfer the ownership before leaving the channel".to_string(),
+        });
+    }
+
+    let channel_result = sqlx::query_as::<_, ChannelDB>("select * from channel where id=$1")
+        .bind(membership.channel_id)
+        .fetch_one(&app_state.database)
+        .await;
+
+    if channel_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if channel_result.unwrap().is_direct {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Direct conversations can not be left".to_string(),
+        });
+    }
+
+    let delete_result = sqlx::query("delete from membership where user_id=$1 and channel_id=$2")
+        .bind(membership.user_id)
+        .bind(membership.channel_id)
+        .execute(&app_state.database)
+        .await;
+
+    if delete_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue leaving the channel".to_string(),
+        });
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_member_removed =
+        PublishedMemberRemoved::new(membership.user_id, user_data.user_id);
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_member_removed).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(membership.channel_id, json_message);
+    }
+    HttpResponse::Ok().json("left the channel")
+}
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::channel_invite::ChannelInviteDb,
+    responses::general_error::GeneralError,
+    AppState,
+};
+
+pub async fn list_invites(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    channel_id: web::Path<i32>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+    let channel_id = channel_id.into_inner();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        channel_id,
+        ChannelPermission::ManageInvites,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let invites_result = sqlx::query_as::<_, ChannelInviteDb>(
+        "select * from channel_invites
+        where channel_id = $1
+        and revoked_at is null
+        and (expires_at is null or expires_at > now())
+        and (max_uses is null or uses < max_uses)
+        order by id",
+    )
+    .bind(channel_id)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if invites_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(invites_result.unwrap())
+}
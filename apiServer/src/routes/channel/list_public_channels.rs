@@ -0,0 +1,73 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    middlewares::auth_middleware::UserData, models::channel::PublicChannel,
+    responses::general_error::GeneralError,
+    validators::public_channels_query_type::PublicChannelsQuery, AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+#[derive(serde::Serialize)]
+struct PublicChannelsResponse {
+    channels: Vec<PublicChannel>,
+    has_more: bool,
+}
+
+pub async fn list_public_channels(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    channels_query: web::Query<PublicChannelsQuery>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = channels_query.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let limit = channels_query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    // one extra row is fetched to know if there is another page
+    let channels_result = sqlx::query_as::<_, PublicChannel>(
+        "select c.id, c.name,
+        (select count(*) from membership m where m.channel_id = c.id) as member_count
+        from channel c
+        where c.is_public and c.archived_at is null
+        and ($1::text is null or position(lower($1) in lower(c.name)) > 0)
+        and ($2::int is null or c.id > $2)
+        order by c.id
+        limit $3",
+    )
+    .bind(&channels_query.search)
+    .bind(channels_query.after)
+    .bind(limit + 1)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if channels_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let mut channels = channels_result.unwrap();
+    let has_more = channels.len() as i64 > limit;
+    channels.truncate(limit as usize);
+
+    HttpResponse::Ok().json(PublicChannelsResponse { channels, has_more })
+}
@@ -0,0 +1,77 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::outgoing_webhook::{OutgoingWebhookDb, WebhookDeliveryDb},
+    responses::general_error::GeneralError,
+    AppState,
+};
+
+// the log is for debugging a receiver, the most recent deliveries are enough
+const DELIVERY_LOG_LIMIT: i64 = 100;
+
+pub async fn list_webhook_deliveries(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    webhook_id: web::Path<i32>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+    let webhook_id = webhook_id.into_inner();
+
+    let webhook_result =
+        sqlx::query_as::<_, OutgoingWebhookDb>("select * from outgoing_webhooks where id = $1")
+            .bind(webhook_id)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if webhook_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if webhook_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Webhook not found".to_string(),
+        });
+    }
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        webhook_result.unwrap().unwrap().channel_id,
+        ChannelPermission::ManageWebhooks,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let deliveries_result = sqlx::query_as::<_, WebhookDeliveryDb>(
+        "select * from webhook_deliveries
+        where webhook_id = $1
+        order by id desc
+        limit $2",
+    )
+    .bind(webhook_id)
+    .bind(DELIVERY_LOG_LIMIT)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if deliveries_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(deliveries_result.unwrap())
+}
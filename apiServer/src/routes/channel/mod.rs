@@ -1,3 +1,24 @@
 pub mod add_user_to_channel;
+pub mod archive_channel;
 pub mod create_channel;
+pub mod create_direct_channel;
+pub mod create_incoming_webhook;
+pub mod create_invite;
+pub mod create_outgoing_webhook;
+pub mod delete_channel;
 pub mod get_user_channels;
+pub mod join_channel;
+pub mod leave_channel;
+pub mod list_incoming_webhooks;
+pub mod list_invites;
+pub mod list_outgoing_webhooks;
+pub mod list_public_channels;
+pub mod list_webhook_deliveries;
+pub mod redeem_invite;
+pub mod remove_member;
+pub mod revoke_incoming_webhook;
+pub mod revoke_invite;
+pub mod revoke_outgoing_webhook;
+pub mod transfer_ownership;
+pub mod update_channel;
+pub mod update_member_role;
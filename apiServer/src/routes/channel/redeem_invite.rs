@@ -0,0 +1,158 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    middlewares::auth_middleware::UserData,
+    models::{channel_invite::ChannelInviteDb, membership::MembershipDb},
+    responses::general_error::GeneralError,
+    validators::invite_code_type::InviteCode,
+    AppState,
+};
+
+pub async fn redeem_invite(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    invite_code_data: web::Json<InviteCode>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = invite_code_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    // the row lock keeps concurrent redeems from going over max_uses
+    let invite_result = sqlx::query_as::<_, ChannelInviteDb>(
+        "select * from channel_invites where code=$1 for update",
+    )
+    .bind(&invite_code_data.0.code)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if invite_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if invite_result.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Invite not found".to_string(),
+        });
+    }
+
+    let invite = invite_result.unwrap().unwrap();
+
+    let is_expired = invite
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now());
+    let is_used_up = invite
+        .max_uses
+        .is_some_and(|max_uses| invite.uses >= max_uses);
+
+    if invite.revoked_at.is_some() || is_expired || is_used_up {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invite is no longer valid".to_string(),
+        });
+    }
+
+    let membership_result = sqlx::query_as::<_, MembershipDb>(
+        "select * from membership where user_id=$1 and channel_id=$2",
+    )
+    .bind(user_data.user_id)
+    .bind(invite.channel_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if membership_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if membership_result.unwrap().is_some() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Already part of this channel".to_string(),
+        });
+    }
+
+    let new_member = sqlx::query_as::<_, MembershipDb>(
+        "insert into membership(user_id, channel_id) values ($1,$2) returning *",
+    )
+    .bind(user_data.user_id)
+    .bind(invite.channel_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if new_member.is_err() || new_member.as_ref().unwrap().is_none() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue rolling back the transaction".to_string(),
+            });
+        }
+
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue inserting to the database".to_string(),
+        });
+    }
+
+    let update_uses_result =
+        sqlx::query("update channel_invites set uses = uses + 1 where id = $1")
+            .bind(invite.id)
+            .execute(transaction.as_mut())
+            .await;
+
+    if update_uses_result.is_err() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue rolling back the transaction".to_string(),
+            });
+        }
+
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue redeeming the invite".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(new_member.unwrap().unwrap())
+}
@@ -0,0 +1,133 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission, PermissionCheck,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{
+        membership::MembershipDb, published_events::PublishedMemberRemoved, user::UserFromDB,
+    },
+    responses::general_error::GeneralError,
+    validators::remove_member_type::RemoveMember,
+    AppState,
+};
+
+pub async fn remove_member(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    remove_member_data: web::Json<RemoveMember>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = remove_member_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if remove_member_data.0.username == user_data.username {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Use leave to remove yourself from a channel".to_string(),
+        });
+    }
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        remove_member_data.0.channel_id,
+        ChannelPermission::RemoveMembers,
+    )
+    .await;
+
+    let actor_role = match permission_result {
+        Ok(PermissionCheck::Allowed(membership)) => membership.role,
+        _ => return permission_error_response(&permission_result).unwrap(),
+    };
+
+    let user_result = sqlx::query_as::<_, UserFromDB>(
+        "select * from users where username=$1 and deleted_at is null",
+    )
+    .bind(&remove_member_data.0.username)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if user_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if user_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let target_membership_result = sqlx::query_as::<_, MembershipDb>(
+        "select * from membership where user_id=$1 and channel_id=$2",
+    )
+    .bind(user_result.unwrap().unwrap().id)
+    .bind(remove_member_data.0.channel_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if target_membership_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if target_membership_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User is not a member of this channel".to_string(),
+        });
+    }
+
+    let target_membership = target_membership_result.unwrap().unwrap();
+
+    if target_membership.role.rank() >= actor_role.rank() {
+        return HttpResponse::Unauthorized().json(GeneralError {
+            message: "You can only remove members below your own role".to_string(),
+        });
+    }
+
+    let delete_result = sqlx::query("delete from membership where user_id=$1 and channel_id=$2")
+        .bind(target_membership.user_id)
+        .bind(target_membership.channel_id)
+        .execute(&app_state.database)
+        .await;
+
+    if delete_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue removing the member".to_string(),
+        });
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_member_removed =
+        PublishedMemberRemoved::new(target_membership.user_id, user_data.user_id);
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_member_removed).unwrap();
+        let _ =
+            redis_conn_mut.publish::<i32, String, ()>(target_membership.channel_id, json_message);
+    }
+    HttpResponse::Ok().json("member removed")
+}
@@ -0,0 +1,94 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::channel_invite::ChannelInviteDb,
+    responses::general_error::GeneralError,
+    validators::invite_code_type::InviteCode,
+    AppState,
+};
+
+pub async fn revoke_invite(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    invite_code_data: web::Json<InviteCode>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = invite_code_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let invite_result =
+        sqlx::query_as::<_, ChannelInviteDb>("select * from channel_invites where code=$1")
+            .bind(&invite_code_data.0.code)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if invite_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if invite_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Invite not found".to_string(),
+        });
+    }
+
+    let invite = invite_result.unwrap().unwrap();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        invite.channel_id,
+        ChannelPermission::ManageInvites,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    if invite.revoked_at.is_some() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invite already revoked".to_string(),
+        });
+    }
+
+    let revoked_invite = sqlx::query_as::<_, ChannelInviteDb>(
+        "update channel_invites set revoked_at = now() where id = $1 returning *",
+    )
+    .bind(invite.id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if revoked_invite.is_err() || revoked_invite.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the invite".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(revoked_invite.unwrap().unwrap())
+}
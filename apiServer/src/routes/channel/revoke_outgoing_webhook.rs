@@ -0,0 +1,123 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::outgoing_webhook::OutgoingWebhookDb,
+    responses::general_error::GeneralError,
+    validators::webhook_id_type::WebhookId,
+    AppState,
+};
+
+pub async fn revoke_outgoing_webhook(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    webhook_id_data: web::Json<WebhookId>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = webhook_id_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let webhook_result =
+        sqlx::query_as::<_, OutgoingWebhookDb>("select * from outgoing_webhooks where id = $1")
+            .bind(webhook_id_data.0.webhook_id)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if webhook_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if webhook_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Webhook not found".to_string(),
+        });
+    }
+
+    let webhook = webhook_result.unwrap().unwrap();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        webhook.channel_id,
+        ChannelPermission::ManageWebhooks,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    if webhook.revoked_at.is_some() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Webhook already revoked".to_string(),
+        });
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let revoked_webhook = sqlx::query_as::<_, OutgoingWebhookDb>(
+        "update outgoing_webhooks set revoked_at = now() where id = $1 returning *",
+    )
+    .bind(webhook.id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    // whatever is still queued would otherwise keep going to the old url
+    let cancel_result = sqlx::query(
+        "update webhook_deliveries set status = 'failed', last_error = 'Webhook revoked'
+        where webhook_id = $1 and status = 'pending'",
+    )
+    .bind(webhook.id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if revoked_webhook.is_err()
+        || revoked_webhook.as_ref().unwrap().is_none()
+        || cancel_result.is_err()
+    {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the webhook".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(revoked_webhook.unwrap().unwrap())
+}
@@ -0,0 +1,193 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::{
+        channel_ownership::transfer_channel_ownership,
+        channel_permission::{
+            check_channel_permission, permission_error_response, ChannelPermission,
+        },
+    },
+    middlewares::auth_middleware::UserData,
+    models::{
+        channel::ChannelDB,
+        membership::MembershipDb,
+        published_events::PublishedOwnershipTransferred,
+        user::{UserFromDB, UserKind},
+    },
+    responses::general_error::GeneralError,
+    validators::transfer_ownership_type::TransferOwnership,
+    AppState,
+};
+
+pub async fn transfer_ownership(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    transfer_ownership_data: web::Json<TransferOwnership>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = transfer_ownership_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+    let channel_id = transfer_ownership_data.0.channel_id;
+
+    if transfer_ownership_data.0.username == user_data.username {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "You already own this channel".to_string(),
+        });
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    // the row lock keeps two concurrent transfers from both going through
+    let channel_result =
+        sqlx::query_as::<_, ChannelDB>("select * from channel where id=$1 for update")
+            .bind(channel_id)
+            .fetch_optional(transaction.as_mut())
+            .await;
+
+    if channel_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let permission_result = check_channel_permission(
+        transaction.as_mut(),
+        user_data.user_id,
+        channel_id,
+        ChannelPermission::TransferOwnership,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        let _ = transaction.rollback().await;
+        return error_response;
+    }
+
+    let user_result = sqlx::query_as::<_, UserFromDB>(
+        "select * from users where username=$1 and deleted_at is null",
+    )
+    .bind(&transfer_ownership_data.0.username)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if user_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if user_result.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    if user_result.as_ref().unwrap().as_ref().unwrap().kind == UserKind::Bot {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Bots can not own channels".to_string(),
+        });
+    }
+
+    let target_membership_result = sqlx::query_as::<_, MembershipDb>(
+        "select * from membership where user_id=$1 and channel_id=$2",
+    )
+    .bind(user_result.unwrap().unwrap().id)
+    .bind(channel_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if target_membership_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if target_membership_result.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User is not a member of this channel".to_string(),
+        });
+    }
+
+    let new_owner_id = target_membership_result.unwrap().unwrap().user_id;
+
+    let transfer_result =
+        transfer_channel_ownership(transaction.as_mut(), channel_id, new_owner_id).await;
+
+    if transfer_result.is_err() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue rolling back the transaction".to_string(),
+            });
+        }
+
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue transferring the ownership".to_string(),
+        });
+    }
+
+    let updated_channel_result =
+        sqlx::query_as::<_, ChannelDB>("select * from channel where id=$1")
+            .bind(channel_id)
+            .fetch_one(transaction.as_mut())
+            .await;
+
+    if updated_channel_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_ownership_transferred =
+        PublishedOwnershipTransferred::new(user_data.user_id, channel_id, new_owner_id);
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_ownership_transferred).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(channel_id, json_message);
+    }
+
+    HttpResponse::Ok().json(updated_channel_result.unwrap())
+}
@@ -0,0 +1,124 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{channel::ChannelDB, published_events::PublishedChannelUpdate},
+    responses::general_error::GeneralError,
+    validators::update_channel_type::UpdateChannel,
+    AppState,
+};
+
+pub async fn update_channel(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    update_channel_data: web::Json<UpdateChannel>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = update_channel_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+    let channel_id = update_channel_data.0.channel_id;
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        channel_id,
+        ChannelPermission::UpdateChannel,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    if let Some(channel_name) = &update_channel_data.0.channel_name {
+        let existing_channel_with_same_name = sqlx::query_as::<_, ChannelDB>(
+            "select * from channel where name = $1 and not is_direct and id <> $2",
+        )
+        .bind(channel_name)
+        .bind(channel_id)
+        .fetch_optional(&app_state.database)
+        .await;
+
+        if existing_channel_with_same_name.is_err() {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue talking to the database".to_string(),
+            });
+        }
+
+        if existing_channel_with_same_name.unwrap().is_some() {
+            return HttpResponse::BadRequest().json(GeneralError {
+                message: "Channel with this name already exists".to_string(),
+            });
+        }
+    }
+
+    let updated_channel_result = sqlx::query_as::<_, ChannelDB>(
+        "update channel set
+        name = coalesce($1, name),
+        topic = coalesce($2, topic),
+        description = coalesce($3, description)
+        where id = $4 returning *",
+    )
+    .bind(&update_channel_data.0.channel_name)
+    .bind(&update_channel_data.0.topic)
+    .bind(&update_channel_data.0.description)
+    .bind(channel_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    // a rename racing another one still trips the unique index
+    if let Err(sqlx::Error::Database(database_error)) = &updated_channel_result {
+        if database_error.is_unique_violation() {
+            return HttpResponse::BadRequest().json(GeneralError {
+                message: "Channel with this name already exists".to_string(),
+            });
+        }
+    }
+
+    if updated_channel_result.is_err() || updated_channel_result.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue updating the channel".to_string(),
+        });
+    }
+
+    let updated_channel = updated_channel_result.unwrap().unwrap();
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_channel_update = PublishedChannelUpdate::new(
+        user_data.user_id,
+        updated_channel.id,
+        updated_channel.name.clone(),
+        updated_channel.topic.clone(),
+        updated_channel.description.clone(),
+    );
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_channel_update).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(updated_channel.id, json_message);
+    }
+
+    HttpResponse::Ok().json(updated_channel)
+}
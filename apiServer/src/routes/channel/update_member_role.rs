@@ -0,0 +1,136 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission, PermissionCheck,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{
+        membership::{ChannelRole, MembershipDb},
+        user::UserFromDB,
+    },
+    responses::general_error::GeneralError,
+    validators::update_member_role_type::UpdateMemberRole,
+    AppState,
+};
+
+pub async fn update_member_role(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    update_role_data: web::Json<UpdateMemberRole>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = update_role_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if update_role_data.0.username == user_data.username {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "You can not change your own role".to_string(),
+        });
+    }
+
+    if update_role_data.0.role == ChannelRole::Owner {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Channel ownership can not be assigned through roles".to_string(),
+        });
+    }
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        update_role_data.0.channel_id,
+        ChannelPermission::ManageRoles,
+    )
+    .await;
+
+    let actor_role = match permission_result {
+        Ok(PermissionCheck::Allowed(membership)) => membership.role,
+        _ => return permission_error_response(&permission_result).unwrap(),
+    };
+
+    let user_result = sqlx::query_as::<_, UserFromDB>(
+        "select * from users where username=$1 and deleted_at is null",
+    )
+    .bind(&update_role_data.0.username)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if user_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if user_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let target_membership_result = sqlx::query_as::<_, MembershipDb>(
+        "select * from membership where user_id=$1 and channel_id=$2",
+    )
+    .bind(user_result.unwrap().unwrap().id)
+    .bind(update_role_data.0.channel_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if target_membership_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if target_membership_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User is not a member of this channel".to_string(),
+        });
+    }
+
+    let target_membership = target_membership_result.unwrap().unwrap();
+
+    // nobody can touch someone at or above their own role, or hand out a role that high
+    if target_membership.role.rank() >= actor_role.rank()
+        || update_role_data.0.role.rank() >= actor_role.rank()
+    {
+        return HttpResponse::Unauthorized().json(GeneralError {
+            message: "You can only manage roles below your own".to_string(),
+        });
+    }
+
+    let updated_membership = sqlx::query_as::<_, MembershipDb>(
+        "update membership set role=$1 where user_id=$2 and channel_id=$3 returning *",
+    )
+    .bind(update_role_data.0.role)
+    .bind(target_membership.user_id)
+    .bind(target_membership.channel_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if updated_membership.is_err() || updated_membership.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue updating the role".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(updated_membership.unwrap().unwrap())
+}
@@ -0,0 +1,106 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{message::MessageContentDb, published_events::PublishedReaction},
+    responses::general_error::GeneralError,
+    validators::reaction_type::ReactionType,
+    AppState,
+};
+
+pub async fn add_reaction(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    reaction_data: web::Json<ReactionType>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if let Err(e) = reaction_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let message_result =
+        sqlx::query_as::<_, MessageContentDb>("select * from messages where id=$1")
+            .bind(reaction_data.0.message_id)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if message_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let message = message_result.unwrap();
+    if message.is_none() || message.as_ref().unwrap().deleted_at.is_some() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Message not found".to_string(),
+        });
+    }
+
+    let message = message.unwrap();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        message.channel_id,
+        ChannelPermission::ReactToMessages,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let insert_result = sqlx::query(
+        "insert into message_reactions(message_id, user_id, emoji) values ($1, $2, $3)
+        on conflict do nothing",
+    )
+    .bind(message.id)
+    .bind(user_data.user_id)
+    .bind(&reaction_data.0.emoji)
+    .execute(&app_state.database)
+    .await;
+
+    if insert_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue inserting to the database".to_string(),
+        });
+    }
+
+    if insert_result.unwrap().rows_affected() == 0 {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Already reacted with this emoji".to_string(),
+        });
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_reaction =
+        PublishedReaction::added(message.id, reaction_data.0.emoji, user_data.user_id);
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_reaction).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(message.channel_id, json_message);
+    }
+    HttpResponse::Ok().json("reaction added")
+}
@@ -0,0 +1,147 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{message::MessageContentDb, published_events::PublishedDelete},
+    validators::message_delete_type::MessageDeleteType,
+    AppState,
+};
+
+// published without a sender so the deleting user's other devices get the tombstone too
+pub async fn delete_message(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    delete_data: web::Json<MessageDeleteType>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue talking to the database".to_string(),
+            },
+        );
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if let Err(e) = delete_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(crate::responses::general_error::GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let existing_message_result =
+        sqlx::query_as::<_, MessageContentDb>("select * from messages where id=$1 for update")
+            .bind(delete_data.0.message_id)
+            .fetch_optional(transaction.as_mut())
+            .await;
+
+    if existing_message_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue talking to the database".to_string(),
+            },
+        );
+    }
+
+    if existing_message_result.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(crate::responses::general_error::GeneralError {
+            message: "Message not found".to_string(),
+        });
+    }
+
+    let existing_message = existing_message_result.unwrap().unwrap();
+
+    if existing_message.deleted_at.is_some() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(crate::responses::general_error::GeneralError {
+            message: "Message already deleted".to_string(),
+        });
+    }
+
+    // senders can retract their own messages, moderators and above can remove anyone's
+    let required_permission = if existing_message.sender_id == user_data.user_id {
+        ChannelPermission::DeleteOwnMessage
+    } else {
+        ChannelPermission::DeleteAnyMessage
+    };
+
+    let permission_result = check_channel_permission(
+        transaction.as_mut(),
+        user_data.user_id,
+        existing_message.channel_id,
+        required_permission,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        let _ = transaction.rollback().await;
+        return error_response;
+    }
+
+    let delete_result =
+        sqlx::query("UPDATE messages SET deleted_at = now(), deleted_by = $1 WHERE id = $2")
+            .bind(user_data.user_id)
+            .bind(existing_message.id)
+            .execute(transaction.as_mut())
+            .await;
+
+    if delete_result.is_err() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(
+                crate::responses::general_error::GeneralError {
+                    message: "Issue rolling back the transaction".to_string(),
+                },
+            );
+        }
+
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue deleting the message".to_string(),
+            },
+        );
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue committing the transaction".to_string(),
+            },
+        );
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_delete = PublishedDelete::new(existing_message.id, user_data.user_id);
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_delete).unwrap();
+        let _ =
+            redis_conn_mut.publish::<i32, String, ()>(existing_message.channel_id, json_message);
+    }
+    HttpResponse::Ok().json("message deleted")
+}
@@ -0,0 +1,182 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{message::MessageContentDb, published_events::PublishedEdit},
+    validators::message_edit_type::MessageEditType,
+    AppState,
+};
+
+#[derive(sqlx::FromRow)]
+struct EditedAt {
+    edited_at: chrono::DateTime<chrono::Utc>,
+}
+
+pub async fn edit_message(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    edit_data: web::Json<MessageEditType>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue talking to the database".to_string(),
+            },
+        );
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if let Err(e) = edit_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(crate::responses::general_error::GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let existing_message_result =
+        sqlx::query_as::<_, MessageContentDb>("select * from messages where id=$1 for update")
+            .bind(edit_data.0.message_id)
+            .fetch_optional(transaction.as_mut())
+            .await;
+
+    if existing_message_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue talking to the database".to_string(),
+            },
+        );
+    }
+
+    if existing_message_result.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(crate::responses::general_error::GeneralError {
+            message: "Message not found".to_string(),
+        });
+    }
+
+    let existing_message = existing_message_result.unwrap().unwrap();
+
+    if existing_message.deleted_at.is_some() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(crate::responses::general_error::GeneralError {
+            message: "Deleted messages can not be edited".to_string(),
+        });
+    }
+
+    if existing_message.sender_id != user_data.user_id {
+        let _ = transaction.rollback().await;
+        return HttpResponse::Unauthorized().json(crate::responses::general_error::GeneralError {
+            message: "You can only edit your own messages".to_string(),
+        });
+    }
+
+    let permission_result = check_channel_permission(
+        transaction.as_mut(),
+        user_data.user_id,
+        existing_message.channel_id,
+        ChannelPermission::SendMessages,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        let _ = transaction.rollback().await;
+        return error_response;
+    }
+
+    let revision_result =
+        sqlx::query("INSERT INTO message_revisions (message_id, message) VALUES ($1, $2)")
+            .bind(existing_message.id)
+            .bind(&existing_message.message)
+            .execute(transaction.as_mut())
+            .await;
+
+    if revision_result.is_err() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(
+                crate::responses::general_error::GeneralError {
+                    message: "Issue rolling back the transaction".to_string(),
+                },
+            );
+        }
+
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue saving the message revision".to_string(),
+            },
+        );
+    }
+
+    let update_result = sqlx::query_as::<_, EditedAt>(
+        "UPDATE messages SET message = $1, edited_at = now() WHERE id = $2 returning edited_at",
+    )
+    .bind(&edit_data.0.message)
+    .bind(existing_message.id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if update_result.is_err() || update_result.as_ref().unwrap().is_none() {
+        let rollback_res = transaction.rollback().await;
+
+        if rollback_res.is_err() {
+            return HttpResponse::InternalServerError().json(
+                crate::responses::general_error::GeneralError {
+                    message: "Issue rolling back the transaction".to_string(),
+                },
+            );
+        }
+
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue editing the message".to_string(),
+            },
+        );
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue committing the transaction".to_string(),
+            },
+        );
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+    let published_edit = PublishedEdit::new(
+        existing_message.id,
+        edit_data.0.message,
+        user_data.user_id,
+        update_result.unwrap().unwrap().edited_at,
+    );
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        let json_message = serde_json::to_string(&published_edit).unwrap();
+        let _ =
+            redis_conn_mut.publish::<i32, String, ()>(existing_message.channel_id, json_message);
+    }
+    HttpResponse::Ok().json("message edited")
+}
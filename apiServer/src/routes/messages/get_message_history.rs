@@ -0,0 +1,109 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission,
+    },
+    middlewares::auth_middleware::UserData,
+    models::message::MessageWithSender,
+    responses::general_error::GeneralError,
+    validators::message_history_type::MessageHistoryQuery,
+    AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+#[derive(serde::Serialize)]
+struct MessageHistoryResponse {
+    messages: Vec<MessageWithSender>,
+    has_more: bool,
+}
+
+pub async fn get_message_history(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    channel_id: web::Path<i32>,
+    history_query: web::Query<MessageHistoryQuery>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if let Err(e) = history_query.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let channel_id = channel_id.into_inner();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        channel_id,
+        ChannelPermission::ReadMessages,
+    )
+    .await;
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    let limit = history_query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    // only an "after" cursor means the client is catching up, so walk forward from it;
+    // everything else is scrollback and walks backwards from the newest message
+    let ascending = history_query.after.is_some() && history_query.before.is_none();
+    let order = if ascending { "asc" } else { "desc" };
+
+    // one extra row is fetched to know if there is another page
+    let history_result = sqlx::query_as::<_, MessageWithSender>(&format!(
+        "select m.id, m.sender_id, coalesce(iw.name, u.username) as username,
+        case when m.deleted_at is null then m.message end as message,
+        m.created_at, m.edited_at, m.deleted_at, m.parent_id,
+        (select count(*) from messages r where r.parent_id = m.id and r.deleted_at is null) as reply_count,
+        (select coalesce(json_agg(json_build_object('emoji', x.emoji, 'count', x.count) order by x.emoji), '[]')
+            from (select emoji, count(*) as count from message_reactions where message_id = m.id group by emoji) x) as reactions
+        from messages m join users u on u.id = m.sender_id
+        left join incoming_webhooks iw on iw.integration_user_id = m.sender_id
+        where m.channel_id = $1 and m.parent_id is null
+        and ($2::int is null or m.id < $2)
+        and ($3::int is null or m.id > $3)
+        order by m.id {}
+        limit $4",
+        order
+    ))
+    .bind(channel_id)
+    .bind(history_query.before)
+    .bind(history_query.after)
+    .bind(limit + 1)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if history_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let mut messages = history_result.unwrap();
+    let has_more = messages.len() as i64 > limit;
+    messages.truncate(limit as usize);
+    if !ascending {
+        messages.reverse();
+    }
+
+    HttpResponse::Ok().json(MessageHistoryResponse { messages, has_more })
+}
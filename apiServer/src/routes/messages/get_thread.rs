@@ -0,0 +1,165 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::channel_permission::{
+        check_channel_permission, permission_error_response, ChannelPermission, PermissionCheck,
+    },
+    middlewares::auth_middleware::UserData,
+    models::message::{MessageContentDb, MessageWithSender},
+    responses::general_error::GeneralError,
+    validators::message_history_type::MessageHistoryQuery,
+    AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+
+#[derive(serde::Serialize)]
+struct ThreadResponse {
+    parent: MessageWithSender,
+    replies: Vec<MessageWithSender>,
+    has_more: bool,
+}
+
+pub async fn get_thread(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    message_id: web::Path<i32>,
+    history_query: web::Query<MessageHistoryQuery>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if let Err(e) = history_query.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let message_id = message_id.into_inner();
+
+    let parent_message_result =
+        sqlx::query_as::<_, MessageContentDb>("select * from messages where id=$1")
+            .bind(message_id)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if parent_message_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if parent_message_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Message not found".to_string(),
+        });
+    }
+
+    let parent_message = parent_message_result.unwrap().unwrap();
+
+    let permission_result = check_channel_permission(
+        &app_state.database,
+        user_data.user_id,
+        parent_message.channel_id,
+        ChannelPermission::ReadMessages,
+    )
+    .await;
+
+    // outsiders get the same answer as for a missing message, ids are not probed
+    if let Ok(PermissionCheck::NotMember) = permission_result {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Message not found".to_string(),
+        });
+    }
+
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        return error_response;
+    }
+
+    if parent_message.parent_id.is_some() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Message is a reply, not a thread".to_string(),
+        });
+    }
+
+    let parent_result = sqlx::query_as::<_, MessageWithSender>(
+        "select m.id, m.sender_id, coalesce(iw.name, u.username) as username,
+        case when m.deleted_at is null then m.message end as message,
+        m.created_at, m.edited_at, m.deleted_at, m.parent_id,
+        (select count(*) from messages r where r.parent_id = m.id and r.deleted_at is null) as reply_count,
+        (select coalesce(json_agg(json_build_object('emoji', x.emoji, 'count', x.count) order by x.emoji), '[]')
+            from (select emoji, count(*) as count from message_reactions where message_id = m.id group by emoji) x) as reactions
+        from messages m join users u on u.id = m.sender_id
+        left join incoming_webhooks iw on iw.integration_user_id = m.sender_id
+        where m.id = $1",
+    )
+    .bind(message_id)
+    .fetch_one(&app_state.database)
+    .await;
+
+    if parent_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let limit = history_query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    // same cursor rules as the channel history
+    let ascending = history_query.after.is_some() && history_query.before.is_none();
+    let order = if ascending { "asc" } else { "desc" };
+
+    let replies_result = sqlx::query_as::<_, MessageWithSender>(&format!(
+        "select m.id, m.sender_id, coalesce(iw.name, u.username) as username,
+        case when m.deleted_at is null then m.message end as message,
+        m.created_at, m.edited_at, m.deleted_at, m.parent_id, 0::bigint as reply_count,
+        (select coalesce(json_agg(json_build_object('emoji', x.emoji, 'count', x.count) order by x.emoji), '[]')
+            from (select emoji, count(*) as count from message_reactions where message_id = m.id group by emoji) x) as reactions
+        from messages m join users u on u.id = m.sender_id
+        left join incoming_webhooks iw on iw.integration_user_id = m.sender_id
+        where m.parent_id = $1
+        and ($2::int is null or m.id < $2)
+        and ($3::int is null or m.id > $3)
+        order by m.id {}
+        limit $4",
+        order
+    ))
+    .bind(message_id)
+    .bind(history_query.before)
+    .bind(history_query.after)
+    .bind(limit + 1)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if replies_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let mut replies = replies_result.unwrap();
+    let has_more = replies.len() as i64 > limit;
+    replies.truncate(limit as usize);
+    if !ascending {
+        replies.reverse();
+    }
+
+    HttpResponse::Ok().json(ThreadResponse {
+        parent: parent_result.unwrap(),
+        replies,
+        has_more,
+    })
+}
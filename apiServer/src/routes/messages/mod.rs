@@ -1 +1,7 @@
+pub mod add_reaction;
+pub mod delete_message;
+pub mod edit_message;
+pub mod get_message_history;
+pub mod get_thread;
+pub mod remove_reaction;
 pub mod send_message;
@@ -3,18 +3,21 @@ use redis::Commands;
 use validator::Validate;
 
 use crate::{
+    dbcalls::{
+        channel_permission::{
+            check_channel_permission, permission_error_response, ChannelPermission,
+        },
+        outgoing_webhooks::enqueue_message_created,
+    },
     middlewares::auth_middleware::UserData,
-    models::{membership::MembershipDb, message::MessagesDb},
+    models::{
+        message::{MessageContentDb, MessagesDb},
+        published_events::PublishedMessage,
+    },
     validators::message_type::MessageSendType,
     AppState,
 };
 
-#[derive(serde::Serialize)]
-struct PublishedMessage {
-    message: String,
-    sender: i32,
-}
-
 pub async fn send_message(
     req: HttpRequest,
     app_state: web::Data<AppState>,
@@ -27,8 +30,7 @@ pub async fn send_message(
             },
         );
     }
-    let extensions = req.extensions();
-    let user_data = extensions.get::<UserData>().unwrap();
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
 
     if let Err(e) = message_data.validate() {
         let mut validation_errors: Vec<String> = Vec::new();
@@ -53,38 +55,64 @@ pub async fn send_message(
 
     let mut transaction = transaction_res.unwrap();
 
-    let membership_channel_result = sqlx::query_as::<_, MembershipDb>(
-        "select * from membership where user_id=$1 and channel_id=$2",
+    let permission_result = check_channel_permission(
+        transaction.as_mut(),
+        user_data.user_id,
+        message_data.0.channel_id,
+        ChannelPermission::SendMessages,
     )
-    .bind(user_data.user_id)
-    .bind(message_data.0.channel_id)
-    .fetch_optional(transaction.as_mut())
     .await;
 
-    if membership_channel_result.is_err() || membership_channel_result.as_ref().unwrap().is_none() {
-        let rollback_res = transaction.rollback().await;
+    if let Some(error_response) = permission_error_response(&permission_result) {
+        let _ = transaction.rollback().await;
+        return error_response;
+    }
 
-        if rollback_res.is_err() {
+    if let Some(parent_id) = message_data.0.parent_id {
+        let parent_message_result =
+            sqlx::query_as::<_, MessageContentDb>("select * from messages where id=$1")
+                .bind(parent_id)
+                .fetch_optional(transaction.as_mut())
+                .await;
+
+        if parent_message_result.is_err() {
+            let _ = transaction.rollback().await;
             return HttpResponse::InternalServerError().json(
                 crate::responses::general_error::GeneralError {
-                    message: "Issue rolling back the transaction".to_string(),
+                    message: "Issue talking to the database".to_string(),
                 },
             );
         }
 
-        return HttpResponse::InternalServerError().json(
-            crate::responses::general_error::GeneralError {
-                message: "Issue finding the channel".to_string(),
-            },
-        );
+        let parent_message = parent_message_result.unwrap();
+        if parent_message.is_none()
+            || parent_message.as_ref().unwrap().channel_id != message_data.0.channel_id
+            || parent_message.as_ref().unwrap().deleted_at.is_some()
+        {
+            let _ = transaction.rollback().await;
+            return HttpResponse::NotFound().json(crate::responses::general_error::GeneralError {
+                message: "Parent message not found".to_string(),
+            });
+        }
+
+        // threads are one level deep, replies go to the thread root
+        if parent_message.unwrap().parent_id.is_some() {
+            let _ = transaction.rollback().await;
+            return HttpResponse::BadRequest().json(
+                crate::responses::general_error::GeneralError {
+                    message: "Can not reply to a reply".to_string(),
+                },
+            );
+        }
     }
 
     let send_message_result = sqlx::query_as::<_, MessagesDb>(
-        "INSERT INTO messages (sender_id, channel_id, message) VALUES ($1, $2, $3) returning *",
+        "INSERT INTO messages (sender_id, channel_id, message, parent_id) VALUES ($1, $2, $3, $4) returning *",
     )
     .bind(user_data.user_id)
     .bind(message_data.0.channel_id)
     .bind(&message_data.0.message)
+    .bind(message_data.0.parent_id)
     .fetch_optional(transaction.as_mut())
     .await;
 
@@ -106,6 +134,19 @@ pub async fn send_message(
         );
     }
 
+    let new_message = send_message_result.unwrap().unwrap();
+
+    let enqueue_result = enqueue_message_created(transaction.as_mut(), new_message.id).await;
+
+    if enqueue_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: "Issue sending the message".to_string(),
+            },
+        );
+    }
+
     let commit_result = transaction.commit().await;
     if commit_result.is_err() {
         return HttpResponse::InternalServerError().json(
@@ -116,10 +157,13 @@ pub async fn send_message(
     }
 
     let redis_connection_result = app_state.redis_pool.get();
-    let published_message = PublishedMessage {
-        message: message_data.0.message,
-        sender: user_data.user_id,
-    };
+    let published_message = PublishedMessage::new(
+        new_message.id,
+        message_data.0.message,
+        user_data.user_id,
+        new_message.created_at,
+        message_data.0.parent_id,
+    );
 
     if let Ok(mut redis_conn_mut) = redis_connection_result {
         let json_message = serde_json::to_string(&published_message).unwrap();
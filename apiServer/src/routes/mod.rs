@@ -2,3 +2,4 @@ pub mod channel;
 pub mod messages;
 pub mod test;
 pub mod user;
+pub mod webhooks;
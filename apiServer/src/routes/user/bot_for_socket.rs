@@ -0,0 +1,30 @@
+use actix_web::{web, HttpResponse, Responder};
+
+use crate::{
+    dbcalls::bots::{find_bot_by_token, BOT_TOKEN_PREFIX},
+    responses::general_error::GeneralError,
+    validators::get_socket_bot_type::WebSocketBot,
+    AppState,
+};
+
+/// Lets the websocket server accept a bot by its token alone, answers with the
+/// bot's user id or null.
+pub async fn bot_for_socket(
+    app_state: web::Data<AppState>,
+    ws_bot_data: web::Json<WebSocketBot>,
+) -> impl Responder {
+    if app_state.api_secret != ws_bot_data.0.endpoint_secret {
+        return HttpResponse::Unauthorized().json(GeneralError {
+            message: "You are not authorized to visit this endpoint".to_string(),
+        });
+    }
+
+    if !ws_bot_data.0.token.starts_with(BOT_TOKEN_PREFIX) {
+        return HttpResponse::Ok().json(None::<i32>);
+    }
+
+    match find_bot_by_token(&app_state, &ws_bot_data.0.token).await {
+        Ok(Some(bot)) => HttpResponse::Ok().json(Some(bot.id)),
+        _ => HttpResponse::Ok().json(None::<i32>),
+    }
+}
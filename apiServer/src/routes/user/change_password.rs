@@ -0,0 +1,179 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::sessions::session_redis_key, middlewares::auth_middleware::UserData,
+    models::user::UserFromDBWithPassword, responses::general_error::GeneralError,
+    validators::change_password_type::ChangePassword, AppState,
+};
+
+pub async fn change_password(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    change_password_data: web::Json<ChangePassword>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = change_password_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if user_data.session_id.is_none() {
+        return HttpResponse::Forbidden().json(GeneralError {
+            message: "Only available to a login session".to_string(),
+        });
+    }
+    let current_session_id = user_data.session_id.clone().unwrap();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let existing_user = sqlx::query_as::<_, UserFromDBWithPassword>(
+        "select * from users where id = $1 and deleted_at is null for update",
+    )
+    .bind(user_data.user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if existing_user.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if existing_user.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let validate_password = bcrypt::verify(
+        &change_password_data.0.current_password,
+        &existing_user.unwrap().unwrap().password,
+    );
+
+    if validate_password.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue validating password".to_string(),
+        });
+    }
+
+    if !validate_password.unwrap() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Incorrect password".to_string(),
+        });
+    }
+
+    let hashed_password = bcrypt::hash(&change_password_data.0.new_password, 12);
+    if hashed_password.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue processing the password".to_string(),
+        });
+    }
+
+    let update_result = sqlx::query("update users set password = $1 where id = $2")
+        .bind(hashed_password.unwrap())
+        .bind(user_data.user_id)
+        .execute(transaction.as_mut())
+        .await;
+
+    if update_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue updating the password".to_string(),
+        });
+    }
+
+    // the session that changed the password is the only one that stays logged in
+    let revoke_sessions_result = sqlx::query_as::<_, (String,)>(
+        "update sessions set revoked_at = now()
+        where user_id = $1 and id <> $2 and revoked_at is null
+        returning id",
+    )
+    .bind(user_data.user_id)
+    .bind(&current_session_id)
+    .fetch_all(transaction.as_mut())
+    .await;
+
+    let revoke_refresh_tokens_result = sqlx::query(
+        "update refresh_tokens set revoked_at = now()
+        where user_id = $1 and family_id <> $2 and revoked_at is null",
+    )
+    .bind(user_data.user_id)
+    .bind(&current_session_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    // a two factor login started with the old password can not be finished either
+    let expire_challenges_result = sqlx::query(
+        "update two_factor_challenges set used_at = now()
+        where user_id = $1 and used_at is null",
+    )
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    // a leaked access token must not outlive the password either
+    let revoke_access_tokens_result = sqlx::query(
+        "update access_tokens set revoked_at = now()
+        where user_id = $1 and revoked_at is null",
+    )
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if revoke_sessions_result.is_err()
+        || revoke_refresh_tokens_result.is_err()
+        || expire_challenges_result.is_err()
+        || revoke_access_tokens_result.is_err()
+    {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the sessions".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    if let Ok(mut redis_conn_mut) = app_state.redis_pool.get() {
+        for (session_id,) in revoke_sessions_result.unwrap().iter() {
+            let _ =
+                redis_conn_mut.del::<String, ()>(session_redis_key(user_data.user_id, session_id));
+        }
+    }
+
+    HttpResponse::Ok().json("password changed")
+}
@@ -0,0 +1,140 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::two_factor::replace_recovery_codes, middlewares::auth_middleware::UserData,
+    responses::general_error::GeneralError, tokens::totp::matching_totp_step,
+    validators::two_factor_code_type::TwoFactorCode, AppState,
+};
+
+#[derive(serde::Serialize)]
+struct ConfirmTwoFactorResponse {
+    #[serde(rename = "recoveryCodes")]
+    recovery_codes: Vec<String>,
+}
+
+pub async fn confirm_two_factor(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    two_factor_code_data: web::Json<TwoFactorCode>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = two_factor_code_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let pending_user = sqlx::query_as::<_, (Option<String>, bool)>(
+        "select totp_secret, totp_enabled_at is not null from users
+        where id = $1 and deleted_at is null
+        for update",
+    )
+    .bind(user_data.user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if pending_user.is_err() || pending_user.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let (totp_secret, already_enabled) = pending_user.unwrap().unwrap();
+
+    if already_enabled {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Two factor authentication is already enabled".to_string(),
+        });
+    }
+
+    if totp_secret.is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Start the two factor enrollment first".to_string(),
+        });
+    }
+
+    let step = matching_totp_step(
+        totp_secret.as_ref().unwrap(),
+        &user_data.username,
+        &two_factor_code_data.0.code,
+        None,
+    );
+
+    if let Err(err_string) = step {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    if step.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invalid two factor code".to_string(),
+        });
+    }
+
+    let enable_result = sqlx::query(
+        "update users set totp_enabled_at = now(), totp_last_used_step = $1 where id = $2",
+    )
+    .bind(step.unwrap().unwrap())
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if enable_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue enabling two factor authentication".to_string(),
+        });
+    }
+
+    let recovery_codes = replace_recovery_codes(transaction.as_mut(), user_data.user_id).await;
+
+    if recovery_codes.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue generating the recovery codes".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    // the plain codes are never shown again
+    HttpResponse::Ok().json(ConfirmTwoFactorResponse {
+        recovery_codes: recovery_codes.unwrap(),
+    })
+}
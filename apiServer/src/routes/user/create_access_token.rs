@@ -0,0 +1,82 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::access_tokens::issue_access_token,
+    middlewares::auth_middleware::UserData,
+    models::access_token::{AccessTokenDb, AccessTokenScope},
+    responses::general_error::GeneralError,
+    validators::create_access_token_type::CreateAccessToken,
+    AppState,
+};
+
+#[derive(serde::Serialize)]
+struct CreatedAccessTokenResponse {
+    #[serde(flatten)]
+    access_token: AccessTokenDb,
+    token: String,
+}
+
+pub async fn create_access_token(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    create_access_token_data: web::Json<CreateAccessToken>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = create_access_token_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let mut scopes: Vec<AccessTokenScope> = Vec::new();
+    for scope in create_access_token_data.0.scopes.iter() {
+        match AccessTokenScope::parse(scope) {
+            Some(scope) if !scopes.contains(&scope) => scopes.push(scope),
+            Some(_) => {}
+            None => {
+                return HttpResponse::BadRequest().json(GeneralError {
+                    message: format!("Unknown scope {}", scope),
+                });
+            }
+        }
+    }
+
+    let created_token = issue_access_token(
+        &app_state,
+        user_data.user_id,
+        &create_access_token_data.0.name,
+        &scopes,
+        create_access_token_data.0.expires_in_days,
+    )
+    .await;
+
+    if let Err(err_string) = created_token {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    let (access_token, token) = created_token.unwrap();
+
+    // the plain token is never shown again
+    HttpResponse::Ok().json(CreatedAccessTokenResponse {
+        access_token,
+        token,
+    })
+}
@@ -0,0 +1,90 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::{bots::generate_bot_token, incoming_webhooks::INTEGRATION_USERNAME_PREFIX},
+    middlewares::auth_middleware::UserData,
+    models::user::UserFromDB,
+    responses::general_error::GeneralError,
+    tokens::hash_token::hash_token,
+    validators::create_bot_type::CreateBot,
+    AppState,
+};
+
+#[derive(serde::Serialize)]
+pub struct BotWithTokenResponse {
+    #[serde(flatten)]
+    pub bot: UserFromDB,
+    pub token: String,
+}
+
+pub async fn create_bot(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    create_bot_data: web::Json<CreateBot>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = create_bot_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    if create_bot_data
+        .0
+        .username
+        .starts_with(INTEGRATION_USERNAME_PREFIX)
+    {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Username already taken, try a different one".to_string(),
+        });
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let token = generate_bot_token();
+
+    let new_bot = sqlx::query_as::<_, UserFromDB>(
+        "insert into users(username, kind, bot_owner_id, bot_token_hash)
+        values ($1, 'bot', $2, $3)
+        returning *",
+    )
+    .bind(&create_bot_data.0.username)
+    .bind(user_data.user_id)
+    .bind(hash_token(&token))
+    .fetch_one(&app_state.database)
+    .await;
+
+    if let Err(sqlx::Error::Database(database_error)) = &new_bot {
+        if database_error.constraint() == Some("users_username_key") {
+            return HttpResponse::BadRequest().json(GeneralError {
+                message: "Username already taken, try a different one".to_string(),
+            });
+        }
+    }
+
+    if new_bot.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue creating the bot".to_string(),
+        });
+    }
+
+    // the plain token is never shown again, regenerate it if it gets lost
+    HttpResponse::Ok().json(BotWithTokenResponse {
+        bot: new_bot.unwrap(),
+        token,
+    })
+}
@@ -1,7 +1,12 @@
 use actix_web::{web, HttpResponse, Responder};
 use validator::Validate;
 
-use crate::{validators::create_user_type::User, AppState};
+use crate::{
+    dbcalls::incoming_webhooks::INTEGRATION_USERNAME_PREFIX,
+    mailer::emails::send_verification_email,
+    validators::{create_user_type::User, email_type::normalize_email},
+    AppState,
+};
 
 pub async fn create_user(
     app_state: web::Data<AppState>,
@@ -21,6 +26,17 @@ pub async fn create_user(
         );
     }
 
+    // those names are handed out to integration users
+    if create_user_data
+        .0
+        .username
+        .starts_with(INTEGRATION_USERNAME_PREFIX)
+    {
+        return HttpResponse::BadRequest().json(crate::responses::general_error::GeneralError {
+            message: "Username already taken, try a different one".to_string(),
+        });
+    }
+
     let existing_user = sqlx::query_as::<_, crate::models::user::UserFromDB>(
         "select * from users where username = $1",
     )
@@ -51,14 +67,27 @@ pub async fn create_user(
         );
     }
 
+    let email = create_user_data.0.email.as_deref().map(normalize_email);
+
     let new_user = sqlx::query_as::<_, crate::models::user::UserFromDB>(
-        "insert into users(username, password) values($1, $2) returning *",
+        "insert into users(username, password, email) values($1, $2, $3) returning *",
     )
     .bind(create_user_data.0.username)
     .bind(hashed_password.unwrap())
+    .bind(&email)
     .fetch_optional(&app_state.database)
     .await;
 
+    if let Err(sqlx::Error::Database(database_error)) = &new_user {
+        if database_error.constraint() == Some("users_email_key") {
+            return HttpResponse::BadRequest().json(
+                crate::responses::general_error::GeneralError {
+                    message: "Email already in use".to_string(),
+                },
+            );
+        }
+    }
+
     if new_user.is_err() {
         return HttpResponse::InternalServerError().json(
             crate::responses::general_error::GeneralError {
@@ -73,5 +102,18 @@ pub async fn create_user(
         });
     }
 
-    HttpResponse::Ok().json(new_user.unwrap().unwrap())
+    let new_user = new_user.unwrap().unwrap();
+
+    // the account is usable without a verified email, a failed send can be retried later
+    if let Some(email) = &email {
+        if let Err(err_string) = send_verification_email(&app_state, new_user.id, email).await {
+            log::warn!(
+                "Verification email for user {} failed: {}",
+                new_user.id,
+                err_string
+            );
+        }
+    }
+
+    HttpResponse::Ok().json(new_user)
 }
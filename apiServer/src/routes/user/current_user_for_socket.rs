@@ -31,9 +31,9 @@ pub async fn current_user_for_socket(
         return HttpResponse::Ok().json(false);
     }
 
-    if redis_connection_result.is_ok() {
-        let key = format!("auth:{}", claims.user_id);
-        let token_redis_result: Result<String, _> = redis_connection_result.unwrap().get(key);
+    if let Ok(mut redis_connection) = redis_connection_result {
+        let key = crate::dbcalls::sessions::session_redis_key(claims.user_id, &claims.session_id);
+        let token_redis_result: Result<String, _> = redis_connection.get(key);
         if let Ok(token_redis) = token_redis_result {
             if token_redis == token {
                 return HttpResponse::Ok().json(true);
@@ -44,14 +44,14 @@ pub async fn current_user_for_socket(
     }
 
     // redis is not connected so use this alternate way
-    let user_exists = crate::dbcalls::check_user_exists::check_user_exists(
+    let session_active = crate::dbcalls::check_session_active::check_session_active(
         claims.user_id,
-        &claims.username,
+        &claims.session_id,
         &app_state,
     )
     .await;
 
-    match user_exists {
+    match session_active {
         Err(_) => HttpResponse::Ok().json(false),
         Ok(_) => HttpResponse::Ok().json(true),
     }
@@ -0,0 +1,97 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::bots::remove_bot, middlewares::auth_middleware::UserData,
+    models::published_events::PublishedMemberRemoved, responses::general_error::GeneralError,
+    validators::bot_id_type::BotId, AppState,
+};
+
+pub async fn delete_bot(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    bot_id_data: web::Json<BotId>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = bot_id_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let owned_bot = sqlx::query_as::<_, (i32,)>(
+        "select id from users
+        where id = $1 and bot_owner_id = $2 and deleted_at is null
+        for update",
+    )
+    .bind(bot_id_data.0.bot_id)
+    .bind(user_data.user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if owned_bot.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if owned_bot.unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Bot not found".to_string(),
+        });
+    }
+
+    let left_channels = remove_bot(transaction.as_mut(), bot_id_data.0.bot_id).await;
+
+    if left_channels.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue removing the bot".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    if let Ok(mut redis_conn_mut) = app_state.redis_pool.get() {
+        for channel_id in left_channels.unwrap().iter() {
+            let published_member_removed =
+                PublishedMemberRemoved::new(bot_id_data.0.bot_id, user_data.user_id);
+            let json_message = serde_json::to_string(&published_member_removed).unwrap();
+            let _ = redis_conn_mut.publish::<i32, String, ()>(*channel_id, json_message);
+        }
+    }
+
+    HttpResponse::Ok().json("bot deleted")
+}
@@ -0,0 +1,301 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::{
+        bots::remove_bot, channel_ownership::promote_longest_standing_member,
+        sessions::session_redis_key,
+    },
+    middlewares::auth_middleware::UserData,
+    models::{
+        channel::ChannelDB,
+        published_events::{
+            PublishedChannelArchived, PublishedMemberRemoved, PublishedOwnershipTransferred,
+        },
+        user::UserFromDBWithPassword,
+    },
+    responses::general_error::GeneralError,
+    validators::delete_user_type::DeleteUser,
+    AppState,
+};
+
+pub async fn delete_user(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    delete_user_data: web::Json<DeleteUser>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = delete_user_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let existing_user = sqlx::query_as::<_, UserFromDBWithPassword>(
+        "select * from users where id = $1 and deleted_at is null for update",
+    )
+    .bind(user_data.user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if existing_user.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if existing_user.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let validate_password = bcrypt::verify(
+        delete_user_data.0.password,
+        &existing_user.unwrap().unwrap().password,
+    );
+
+    if validate_password.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue validating password".to_string(),
+        });
+    }
+
+    if !validate_password.unwrap() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Incorrect password".to_string(),
+        });
+    }
+
+    // the account's bots go with it, before the owned channels look for a successor
+    let owned_bots_result = sqlx::query_as::<_, (i32,)>(
+        "select id from users where bot_owner_id = $1 and deleted_at is null for update",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(transaction.as_mut())
+    .await;
+
+    if owned_bots_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let mut removed_bot_memberships: Vec<(i32, i32)> = Vec::new();
+    for (bot_id,) in owned_bots_result.unwrap().iter() {
+        let remove_result = remove_bot(transaction.as_mut(), *bot_id).await;
+
+        if remove_result.is_err() {
+            let _ = transaction.rollback().await;
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue removing the bots".to_string(),
+            });
+        }
+
+        for channel_id in remove_result.unwrap() {
+            removed_bot_memberships.push((*bot_id, channel_id));
+        }
+    }
+
+    let owned_channels_result = sqlx::query_as::<_, ChannelDB>(
+        "select * from channel where admin_id = $1 and not is_direct for update",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(transaction.as_mut())
+    .await;
+
+    if owned_channels_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    // owned channels go to whoever has been around the longest, channels without a
+    // human left to take over are archived so their history survives
+    let mut transferred_channels: Vec<(i32, i32)> = Vec::new();
+    let mut archived_channels: Vec<i32> = Vec::new();
+    for owned_channel in owned_channels_result.unwrap().iter() {
+        let promote_result = promote_longest_standing_member(
+            transaction.as_mut(),
+            owned_channel.id,
+            user_data.user_id,
+        )
+        .await;
+
+        let handover_result = match promote_result {
+            Ok(Some(new_owner_id)) => {
+                transferred_channels.push((owned_channel.id, new_owner_id));
+                Ok(())
+            }
+            Ok(None) => {
+                archived_channels.push(owned_channel.id);
+                sqlx::query(
+                    "update channel set archived_at = coalesce(archived_at, now()) where id = $1",
+                )
+                .bind(owned_channel.id)
+                .execute(transaction.as_mut())
+                .await
+                .map(|_| ())
+            }
+            Err(e) => Err(e),
+        };
+
+        if handover_result.is_err() {
+            let rollback_res = transaction.rollback().await;
+
+            if rollback_res.is_err() {
+                return HttpResponse::InternalServerError().json(GeneralError {
+                    message: "Issue rolling back the transaction".to_string(),
+                });
+            }
+
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: "Issue handing over the owned channels".to_string(),
+            });
+        }
+    }
+
+    // direct conversations are kept so the other side still has the history
+    let left_channels_result = sqlx::query_as::<_, (i32,)>(
+        "delete from membership m using channel c
+        where c.id = m.channel_id and m.user_id = $1 and not c.is_direct
+        returning m.channel_id",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(transaction.as_mut())
+    .await;
+
+    if left_channels_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue removing the memberships".to_string(),
+        });
+    }
+
+    let revoke_invites_result = sqlx::query(
+        "update channel_invites set revoked_at = now()
+        where created_by = $1 and revoked_at is null",
+    )
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if revoke_invites_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the invites".to_string(),
+        });
+    }
+
+    let revoke_sessions_result = sqlx::query_as::<_, (String,)>(
+        "update sessions set revoked_at = now()
+        where user_id = $1 and revoked_at is null
+        returning id",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(transaction.as_mut())
+    .await;
+
+    let revoke_refresh_tokens_result = sqlx::query(
+        "update refresh_tokens set revoked_at = now()
+        where user_id = $1 and revoked_at is null",
+    )
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if revoke_sessions_result.is_err() || revoke_refresh_tokens_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the sessions".to_string(),
+        });
+    }
+
+    let delete_result = sqlx::query("update users set deleted_at = now() where id = $1")
+        .bind(user_data.user_id)
+        .execute(transaction.as_mut())
+        .await;
+
+    if delete_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue deleting the user".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    let redis_connection_result = app_state.redis_pool.get();
+
+    if let Ok(mut redis_conn_mut) = redis_connection_result {
+        for (session_id,) in revoke_sessions_result.unwrap().iter() {
+            let _ =
+                redis_conn_mut.del::<String, ()>(session_redis_key(user_data.user_id, session_id));
+        }
+
+        for (channel_id, new_owner_id) in transferred_channels.iter() {
+            let published_ownership_transferred =
+                PublishedOwnershipTransferred::new(user_data.user_id, *channel_id, *new_owner_id);
+            let json_message = serde_json::to_string(&published_ownership_transferred).unwrap();
+            let _ = redis_conn_mut.publish::<i32, String, ()>(*channel_id, json_message);
+        }
+
+        for channel_id in archived_channels.iter() {
+            let published_channel_archived =
+                PublishedChannelArchived::new(user_data.user_id, *channel_id, true);
+            let json_message = serde_json::to_string(&published_channel_archived).unwrap();
+            let _ = redis_conn_mut.publish::<i32, String, ()>(*channel_id, json_message);
+        }
+
+        for (bot_id, channel_id) in removed_bot_memberships.iter() {
+            let published_member_removed = PublishedMemberRemoved::new(*bot_id, user_data.user_id);
+            let json_message = serde_json::to_string(&published_member_removed).unwrap();
+            let _ = redis_conn_mut.publish::<i32, String, ()>(*channel_id, json_message);
+        }
+
+        for (channel_id,) in left_channels_result.unwrap().iter() {
+            let published_member_removed =
+                PublishedMemberRemoved::new(user_data.user_id, user_data.user_id);
+            let json_message = serde_json::to_string(&published_member_removed).unwrap();
+            let _ = redis_conn_mut.publish::<i32, String, ()>(*channel_id, json_message);
+        }
+    }
+
+    HttpResponse::Ok().json("account deleted")
+}
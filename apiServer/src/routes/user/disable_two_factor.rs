@@ -0,0 +1,152 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::two_factor::verify_second_factor, middlewares::auth_middleware::UserData,
+    models::user::UserFromDBWithPassword, responses::general_error::GeneralError,
+    validators::disable_two_factor_type::DisableTwoFactor, AppState,
+};
+
+pub async fn disable_two_factor(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    disable_two_factor_data: web::Json<DisableTwoFactor>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = disable_two_factor_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let existing_user = sqlx::query_as::<_, UserFromDBWithPassword>(
+        "select * from users where id = $1 and deleted_at is null",
+    )
+    .bind(user_data.user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if existing_user.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if existing_user.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let existing_user = existing_user.unwrap().unwrap();
+
+    if existing_user.totp_enabled_at.is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Two factor authentication is not enabled".to_string(),
+        });
+    }
+
+    let validate_password =
+        bcrypt::verify(&disable_two_factor_data.0.password, &existing_user.password);
+
+    if validate_password.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue validating password".to_string(),
+        });
+    }
+
+    if !validate_password.unwrap() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Incorrect password".to_string(),
+        });
+    }
+
+    // a stolen session alone is not enough to turn the second factor off
+    let verified = verify_second_factor(
+        transaction.as_mut(),
+        user_data.user_id,
+        &disable_two_factor_data.0.code,
+    )
+    .await;
+
+    if let Err(err_string) = verified {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    if !verified.unwrap() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invalid two factor code".to_string(),
+        });
+    }
+
+    let disable_result = sqlx::query(
+        "update users set totp_secret = null, totp_enabled_at = null, totp_last_used_step = null
+        where id = $1",
+    )
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    let delete_codes_result = sqlx::query("delete from recovery_codes where user_id = $1")
+        .bind(user_data.user_id)
+        .execute(transaction.as_mut())
+        .await;
+
+    let expire_challenges_result = sqlx::query(
+        "update two_factor_challenges set used_at = now()
+        where user_id = $1 and used_at is null",
+    )
+    .bind(user_data.user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if disable_result.is_err() || delete_codes_result.is_err() || expire_challenges_result.is_err()
+    {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue disabling two factor authentication".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json("two factor authentication disabled")
+}
@@ -0,0 +1,61 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    middlewares::auth_middleware::UserData,
+    responses::general_error::GeneralError,
+    tokens::totp::{generate_totp_secret, totp_uri},
+    AppState,
+};
+
+#[derive(serde::Serialize)]
+struct EnrollTwoFactorResponse {
+    secret: String,
+    #[serde(rename = "otpauthUri")]
+    otpauth_uri: String,
+}
+
+pub async fn enroll_two_factor(req: HttpRequest, app_state: web::Data<AppState>) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let secret = generate_totp_secret();
+
+    let otpauth_uri = totp_uri(&secret, &user_data.username);
+    if let Err(err_string) = otpauth_uri {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    // enrolling again before confirming just swaps the pending secret
+    let update_result = sqlx::query(
+        "update users set totp_secret = $1, totp_last_used_step = null
+        where id = $2 and totp_enabled_at is null and deleted_at is null",
+    )
+    .bind(&secret)
+    .bind(user_data.user_id)
+    .execute(&app_state.database)
+    .await;
+
+    if update_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if update_result.unwrap().rows_affected() == 0 {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Two factor authentication is already enabled".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(EnrollTwoFactorResponse {
+        secret,
+        otpauth_uri: otpauth_uri.unwrap(),
+    })
+}
@@ -0,0 +1,79 @@
+use actix_web::{web, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::email_tokens::create_email_token,
+    mailer::{emails::password_reset_email, mail::send_email},
+    models::{email_token::EmailTokenPurpose, user::UserFromDB},
+    responses::general_error::GeneralError,
+    validators::email_type::{normalize_email, EmailAddress},
+    AppState,
+};
+
+pub async fn forgot_password(
+    app_state: web::Data<AppState>,
+    email_data: web::Json<EmailAddress>,
+) -> impl Responder {
+    if let Err(e) = email_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let email = normalize_email(&email_data.0.email);
+
+    // only verified addresses get reset links, a mistyped one could belong to someone else
+    let existing_user = sqlx::query_as::<_, UserFromDB>(
+        "select * from users
+        where email = $1 and email_verified_at is not null and deleted_at is null",
+    )
+    .bind(&email)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if existing_user.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Some(user) = existing_user.unwrap() {
+        let token = create_email_token(
+            &app_state,
+            user.id,
+            &email,
+            EmailTokenPurpose::ResetPassword,
+        )
+        .await;
+
+        let send_result = match token {
+            Ok(token) => {
+                send_email(
+                    app_state.mailer.clone(),
+                    password_reset_email(&app_state.app_url, &email, &token),
+                )
+                .await
+            }
+            Err(err_string) => Err(err_string),
+        };
+
+        if let Err(err_string) = send_result {
+            log::warn!(
+                "Password reset email for user {} failed: {}",
+                user.id,
+                err_string
+            );
+        }
+    }
+
+    // same answer either way so this can not be used to find out who has an account
+    HttpResponse::Ok().json("If the email belongs to an account, a reset link was sent to it")
+}
@@ -0,0 +1,35 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    middlewares::auth_middleware::UserData, models::access_token::AccessTokenDb,
+    responses::general_error::GeneralError, AppState,
+};
+
+pub async fn list_access_tokens(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    // expired ones stay listed so it is clear why a script stopped working
+    let access_tokens_result = sqlx::query_as::<_, AccessTokenDb>(
+        "select * from access_tokens where user_id = $1 and revoked_at is null
+        order by created_at desc",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if access_tokens_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(access_tokens_result.unwrap())
+}
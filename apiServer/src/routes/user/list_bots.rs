@@ -0,0 +1,30 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    middlewares::auth_middleware::UserData, models::user::UserFromDB,
+    responses::general_error::GeneralError, AppState,
+};
+
+pub async fn list_bots(req: HttpRequest, app_state: web::Data<AppState>) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let bots_result = sqlx::query_as::<_, UserFromDB>(
+        "select * from users where bot_owner_id = $1 and deleted_at is null order by id",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if bots_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(bots_result.unwrap())
+}
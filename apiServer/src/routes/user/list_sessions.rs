@@ -0,0 +1,47 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    middlewares::auth_middleware::UserData, models::session::SessionDb,
+    responses::general_error::GeneralError, AppState,
+};
+
+#[derive(serde::Serialize)]
+struct SessionResponse {
+    #[serde(flatten)]
+    session: SessionDb,
+    current: bool,
+}
+
+pub async fn list_sessions(req: HttpRequest, app_state: web::Data<AppState>) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let sessions_result = sqlx::query_as::<_, SessionDb>(
+        "select * from sessions where user_id = $1 and revoked_at is null
+        order by last_seen_at desc",
+    )
+    .bind(user_data.user_id)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if sessions_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let sessions: Vec<SessionResponse> = sessions_result
+        .unwrap()
+        .into_iter()
+        .map(|session| SessionResponse {
+            current: user_data.session_id.as_ref() == Some(&session.id),
+            session,
+        })
+        .collect();
+
+    HttpResponse::Ok().json(sessions)
+}
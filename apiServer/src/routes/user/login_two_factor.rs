@@ -0,0 +1,170 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::{
+        login_attempts::{record_failed_login, FailedLogin},
+        two_factor::{consume_two_factor_challenge, verify_second_factor},
+    },
+    models::login_attempt::LoginFailureReason,
+    responses::general_error::GeneralError,
+    routes::user::login_user::start_login_session,
+    validators::login_two_factor_type::LoginTwoFactor,
+    AppState,
+};
+
+pub async fn login_two_factor(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    login_two_factor_data: web::Json<LoginTwoFactor>,
+) -> impl Responder {
+    if let Err(e) = login_two_factor_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let peer_address = req
+        .connection_info()
+        .peer_addr()
+        .unwrap_or("unknown")
+        .to_string();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let challenge = consume_two_factor_challenge(
+        transaction.as_mut(),
+        &login_two_factor_data.0.challenge_token,
+    )
+    .await;
+
+    if challenge.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if challenge.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Login expired, login with your password again".to_string(),
+        });
+    }
+
+    let challenge = challenge.unwrap().unwrap();
+
+    let username = sqlx::query_as::<_, (String,)>(
+        "select username from users where id = $1 and deleted_at is null",
+    )
+    .bind(challenge.user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if username.is_err() || username.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let (username,) = username.unwrap().unwrap();
+
+    // codes are guessed against the same counters as passwords
+    let retry_after =
+        app_state
+            .login_throttle
+            .retry_after(&app_state.redis_pool, &username, &peer_address);
+
+    if let Some(retry_after) = retry_after {
+        let _ = transaction.rollback().await;
+        return HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(GeneralError {
+                message: format!(
+                    "Too many failed login attempts, try again in {} seconds",
+                    retry_after
+                ),
+            });
+    }
+
+    let verified = verify_second_factor(
+        transaction.as_mut(),
+        challenge.user_id,
+        &login_two_factor_data.0.code,
+    )
+    .await;
+
+    if let Err(err_string) = verified {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    if !verified.unwrap() {
+        // rolling back keeps the challenge usable for another try
+        let _ = transaction.rollback().await;
+
+        let locked_for_seconds = app_state.login_throttle.record_failure(
+            &app_state.redis_pool,
+            &username,
+            &peer_address,
+        );
+        let user_agent = req
+            .headers()
+            .get("user-agent")
+            .and_then(|user_agent| user_agent.to_str().ok());
+        record_failed_login(
+            &app_state,
+            FailedLogin {
+                username: &username,
+                user_id: Some(challenge.user_id),
+                ip_address: Some(&peer_address),
+                user_agent,
+                reason: LoginFailureReason::WrongTwoFactorCode,
+                locked_for_seconds,
+            },
+        )
+        .await;
+
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invalid two factor code".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    app_state
+        .login_throttle
+        .clear(&app_state.redis_pool, &username);
+
+    start_login_session(
+        &req,
+        &app_state,
+        challenge.user_id,
+        &username,
+        challenge.device_label,
+    )
+    .await
+}
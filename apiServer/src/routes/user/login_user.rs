@@ -1,23 +1,43 @@
 use actix_web::{
     cookie::{Cookie, SameSite},
-    web, HttpResponse, Responder,
+    web, HttpRequest, HttpResponse, Responder,
 };
 use redis::Commands;
 use validator::Validate;
 
-use crate::{validators::create_user_type::User, AppState};
+use crate::{
+    dbcalls::{
+        login_attempts::{record_failed_login, FailedLogin},
+        sessions::{create_session, session_redis_key, SessionDetails},
+        two_factor::create_two_factor_challenge,
+    },
+    models::login_attempt::LoginFailureReason,
+    validators::login_user_type::LoginUser,
+    AppState,
+};
 
 #[derive(serde::Serialize)]
 struct LoginResponse {
     #[serde(rename = "accessToken")]
     access_token: String,
+    #[serde(rename = "refreshToken")]
+    refresh_token: String,
     #[serde(rename = "userId")]
     user_id: i32,
 }
 
+#[derive(serde::Serialize)]
+struct TwoFactorChallengeResponse {
+    #[serde(rename = "twoFactorRequired")]
+    two_factor_required: bool,
+    #[serde(rename = "challengeToken")]
+    challenge_token: String,
+}
+
 pub async fn login_user(
+    req: HttpRequest,
     app_state: web::Data<AppState>,
-    login_user_data: web::Json<User>,
+    login_user_data: web::Json<LoginUser>,
 ) -> impl Responder {
     if let Err(e) = login_user_data.validate() {
         let mut validation_errors: Vec<String> = Vec::new();
@@ -33,8 +53,37 @@ pub async fn login_user(
         );
     }
 
+    // the socket address, a forwarded header would let a client pick a fresh one per guess
+    let peer_address = req
+        .connection_info()
+        .peer_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let user_agent = req
+        .headers()
+        .get("user-agent")
+        .and_then(|user_agent| user_agent.to_str().ok())
+        .map(|user_agent| user_agent.to_string());
+
+    let retry_after = app_state.login_throttle.retry_after(
+        &app_state.redis_pool,
+        &login_user_data.0.username,
+        &peer_address,
+    );
+
+    if let Some(retry_after) = retry_after {
+        return HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(crate::responses::general_error::GeneralError {
+                message: format!(
+                    "Too many failed login attempts, try again in {} seconds",
+                    retry_after
+                ),
+            });
+    }
+
     let existing_user = sqlx::query_as::<_, crate::models::user::UserFromDBWithPassword>(
-        "select * from users where username = $1",
+        "select * from users where username = $1 and kind = 'human' and deleted_at is null",
     )
     .bind(&login_user_data.0.username)
     .fetch_optional(&app_state.database)
@@ -49,6 +98,24 @@ pub async fn login_user(
     }
 
     if existing_user.as_ref().unwrap().is_none() {
+        let locked_for_seconds = app_state.login_throttle.record_failure(
+            &app_state.redis_pool,
+            &login_user_data.0.username,
+            &peer_address,
+        );
+        record_failed_login(
+            &app_state,
+            FailedLogin {
+                username: &login_user_data.0.username,
+                user_id: None,
+                ip_address: Some(&peer_address),
+                user_agent: user_agent.as_deref(),
+                reason: LoginFailureReason::UnknownUser,
+                locked_for_seconds,
+            },
+        )
+        .await;
+
         return HttpResponse::NotFound().json(crate::responses::general_error::GeneralError {
             message: "User not found".to_string(),
         });
@@ -56,7 +123,7 @@ pub async fn login_user(
 
     let user_data = existing_user.unwrap().unwrap();
 
-    let validate_password = bcrypt::verify(login_user_data.0.password, &user_data.password);
+    let validate_password = bcrypt::verify(&login_user_data.0.password, &user_data.password);
 
     if validate_password.is_err() {
         return HttpResponse::InternalServerError().json(
@@ -67,14 +134,100 @@ pub async fn login_user(
     }
 
     if !validate_password.unwrap() {
+        let locked_for_seconds = app_state.login_throttle.record_failure(
+            &app_state.redis_pool,
+            &login_user_data.0.username,
+            &peer_address,
+        );
+        record_failed_login(
+            &app_state,
+            FailedLogin {
+                username: &login_user_data.0.username,
+                user_id: Some(user_data.id),
+                ip_address: Some(&peer_address),
+                user_agent: user_agent.as_deref(),
+                reason: LoginFailureReason::WrongPassword,
+                locked_for_seconds,
+            },
+        )
+        .await;
+
         return HttpResponse::BadRequest().json(crate::responses::general_error::GeneralError {
             message: "Incorrect password".to_string(),
         });
     }
 
-    let access_token = crate::tokens::generate_token::generate_token(
-        &user_data.username,
+    if user_data.totp_enabled_at.is_some() {
+        let challenge_token =
+            create_two_factor_challenge(&app_state, user_data.id, login_user_data.0.device_label)
+                .await;
+
+        if let Err(err_string) = challenge_token {
+            return HttpResponse::InternalServerError().json(
+                crate::responses::general_error::GeneralError {
+                    message: err_string,
+                },
+            );
+        }
+
+        // the failure counters are only reset once the code checks out too
+        return HttpResponse::Ok().json(TwoFactorChallengeResponse {
+            two_factor_required: true,
+            challenge_token: challenge_token.unwrap(),
+        });
+    }
+
+    app_state
+        .login_throttle
+        .clear(&app_state.redis_pool, &login_user_data.0.username);
+
+    start_login_session(
+        &req,
+        &app_state,
         user_data.id,
+        &user_data.username,
+        login_user_data.0.device_label,
+    )
+    .await
+}
+
+/// Creates the session and hands out its tokens, the last step of every login.
+pub async fn start_login_session(
+    req: &HttpRequest,
+    app_state: &AppState,
+    user_id: i32,
+    username: &str,
+    device_label: Option<String>,
+) -> HttpResponse {
+    let session_details = SessionDetails {
+        device_label,
+        ip_address: req
+            .connection_info()
+            .realip_remote_addr()
+            .map(|address| address.to_string()),
+        user_agent: req
+            .headers()
+            .get("user-agent")
+            .and_then(|user_agent| user_agent.to_str().ok())
+            .map(|user_agent| user_agent.to_string()),
+    };
+
+    let session_id = create_session(app_state, user_id, session_details).await;
+
+    if let Err(err_string) = session_id {
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: err_string,
+            },
+        );
+    }
+
+    let session_id = session_id.unwrap();
+
+    let access_token = crate::tokens::generate_token::generate_token(
+        username,
+        user_id,
+        &session_id,
         &app_state.access_token_secret,
     );
 
@@ -84,15 +237,25 @@ pub async fn login_user(
         });
     }
 
+    let refresh_token =
+        crate::tokens::refresh_token::issue_refresh_token(app_state, user_id, &session_id).await;
+
+    if let Err(err_string) = refresh_token {
+        return HttpResponse::InternalServerError().json(
+            crate::responses::general_error::GeneralError {
+                message: err_string,
+            },
+        );
+    }
+
     let redis_connection_result = app_state.redis_pool.get();
 
-    if redis_connection_result.is_ok() {
-        let key = format!("auth:{}", user_data.id);
-        let expiry_in_seconds = 86400; // 24 hours expiry
-        let _: () = redis_connection_result
-            .unwrap()
-            .set_ex(key, access_token.as_ref().unwrap(), expiry_in_seconds)
-            .unwrap();
+    if let Ok(mut redis_connection) = redis_connection_result {
+        let key = session_redis_key(user_id, &session_id);
+        let expiry_in_seconds = crate::tokens::generate_token::ACCESS_TOKEN_EXPIRY_IN_SECONDS;
+        // a failed write only costs the cache, check_session_active falls back to postgres
+        let _: Result<(), _> =
+            redis_connection.set_ex(key, access_token.as_ref().unwrap(), expiry_in_seconds);
     }
 
     let cookie1 = Cookie::build("accessToken", access_token.as_ref().unwrap())
@@ -102,18 +265,27 @@ pub async fn login_user(
         .same_site(SameSite::None)
         .finish();
 
-    let cookie2 = Cookie::build("userId", format!("{}", user_data.id))
+    let cookie2 = Cookie::build("userId", format!("{}", user_id))
         .path("/")
         .secure(true)
         .http_only(true)
         .same_site(SameSite::None)
         .finish();
 
+    let cookie3 = Cookie::build("refreshToken", refresh_token.as_ref().unwrap())
+        .path("/api/v1/user")
+        .secure(true)
+        .http_only(true)
+        .same_site(SameSite::None)
+        .finish();
+
     HttpResponse::Ok()
         .cookie(cookie1)
         .cookie(cookie2)
+        .cookie(cookie3)
         .json(LoginResponse {
             access_token: access_token.unwrap(),
-            user_id: user_data.id,
+            refresh_token: refresh_token.unwrap(),
+            user_id,
         })
 }
@@ -0,0 +1,42 @@
+use actix_web::{cookie::Cookie, web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    dbcalls::sessions::revoke_session, middlewares::auth_middleware::UserData,
+    responses::general_error::GeneralError, AppState,
+};
+
+pub async fn logout_user(req: HttpRequest, app_state: web::Data<AppState>) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if user_data.session_id.is_none() {
+        return HttpResponse::Forbidden().json(GeneralError {
+            message: "Only available to a login session".to_string(),
+        });
+    }
+    let current_session_id = user_data.session_id.clone().unwrap();
+
+    let revoke_result = revoke_session(&app_state, user_data.user_id, &current_session_id).await;
+
+    if let Err(err_string) = revoke_result {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    // removal cookies have to match the path they were set with
+    let mut response = HttpResponse::Ok().json("logged out");
+    let _ = response.add_removal_cookie(&Cookie::build("accessToken", "").path("/").finish());
+    let _ = response.add_removal_cookie(&Cookie::build("userId", "").path("/").finish());
+    let _ = response.add_removal_cookie(
+        &Cookie::build("refreshToken", "")
+            .path("/api/v1/user")
+            .finish(),
+    );
+
+    response
+}
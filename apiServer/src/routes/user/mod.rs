@@ -1,4 +1,27 @@
+pub mod bot_for_socket;
+pub mod change_password;
+pub mod confirm_two_factor;
+pub mod create_access_token;
+pub mod create_bot;
 pub mod create_user;
 pub mod current_user;
 pub mod current_user_for_socket;
+pub mod delete_bot;
+pub mod delete_user;
+pub mod disable_two_factor;
+pub mod enroll_two_factor;
+pub mod forgot_password;
+pub mod list_access_tokens;
+pub mod list_bots;
+pub mod list_sessions;
+pub mod login_two_factor;
 pub mod login_user;
+pub mod logout_user;
+pub mod refresh_access_token;
+pub mod regenerate_bot_token;
+pub mod reset_password;
+pub mod revoke_access_token;
+pub mod revoke_other_sessions;
+pub mod revoke_user_session;
+pub mod set_email;
+pub mod verify_email;
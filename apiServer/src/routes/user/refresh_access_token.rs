@@ -0,0 +1,149 @@
+use actix_web::{
+    cookie::{Cookie, SameSite},
+    web, HttpRequest, HttpResponse, Responder,
+};
+use redis::Commands;
+
+use crate::{
+    dbcalls::sessions::{revoke_session, session_redis_key, touch_session},
+    models::user::UserFromDB,
+    responses::general_error::GeneralError,
+    tokens::refresh_token::{consume_refresh_token, issue_refresh_token, RefreshTokenUse},
+    validators::refresh_token_type::RefreshTokenRequest,
+    AppState,
+};
+
+#[derive(serde::Serialize)]
+struct RefreshResponse {
+    #[serde(rename = "accessToken")]
+    access_token: String,
+    #[serde(rename = "refreshToken")]
+    refresh_token: String,
+    #[serde(rename = "userId")]
+    user_id: i32,
+}
+
+pub async fn refresh_access_token(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    refresh_token_data: Option<web::Json<RefreshTokenRequest>>,
+) -> impl Responder {
+    // browsers send the cookie, other clients can pass the token in the body
+    let presented_token = match req.cookie("refreshToken") {
+        Some(cookie) => Some(cookie.value().to_string()),
+        None => refresh_token_data.map(|data| data.0.refresh_token),
+    };
+
+    if presented_token.is_none() || presented_token.as_ref().unwrap().is_empty() {
+        return HttpResponse::Unauthorized().json(GeneralError {
+            message: "Unauthorized: Missing refresh token".to_string(),
+        });
+    }
+
+    let consume_result = consume_refresh_token(&app_state, &presented_token.unwrap()).await;
+
+    let consumed_token = match consume_result {
+        Err(err_string) => {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: err_string,
+            })
+        }
+        Ok(RefreshTokenUse::Invalid) => {
+            return HttpResponse::Unauthorized().json(GeneralError {
+                message: "Invalid refresh token, login again".to_string(),
+            })
+        }
+        Ok(RefreshTokenUse::Reused(reused_token)) => {
+            // a spent token coming back means it leaked, nothing from that login is trusted anymore
+            let _ = revoke_session(&app_state, reused_token.user_id, &reused_token.family_id).await;
+            return HttpResponse::Unauthorized().json(GeneralError {
+                message: "Refresh token was already used, login again".to_string(),
+            });
+        }
+        Ok(RefreshTokenUse::Rotated(consumed_token)) => consumed_token,
+    };
+
+    let existing_user =
+        sqlx::query_as::<_, UserFromDB>("select * from users where id = $1 and deleted_at is null")
+            .bind(consumed_token.user_id)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    if existing_user.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if existing_user.as_ref().unwrap().is_none() {
+        return HttpResponse::Unauthorized().json(GeneralError {
+            message: "User not found".to_string(),
+        });
+    }
+
+    let user_data = existing_user.unwrap().unwrap();
+
+    let access_token = crate::tokens::generate_token::generate_token(
+        &user_data.username,
+        user_data.id,
+        &consumed_token.family_id,
+        &app_state.access_token_secret,
+    );
+
+    if access_token.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue generating access token".to_string(),
+        });
+    }
+
+    let refresh_token =
+        issue_refresh_token(&app_state, user_data.id, &consumed_token.family_id).await;
+
+    if let Err(err_string) = refresh_token {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    touch_session(&app_state, &consumed_token.family_id).await;
+
+    let redis_connection_result = app_state.redis_pool.get();
+
+    if let Ok(mut redis_connection) = redis_connection_result {
+        let key = session_redis_key(user_data.id, &consumed_token.family_id);
+        let expiry_in_seconds = crate::tokens::generate_token::ACCESS_TOKEN_EXPIRY_IN_SECONDS;
+        let _: Result<(), _> =
+            redis_connection.set_ex(key, access_token.as_ref().unwrap(), expiry_in_seconds);
+    }
+
+    let cookie1 = Cookie::build("accessToken", access_token.as_ref().unwrap())
+        .path("/")
+        .secure(true)
+        .http_only(true)
+        .same_site(SameSite::None)
+        .finish();
+
+    let cookie2 = Cookie::build("userId", format!("{}", user_data.id))
+        .path("/")
+        .secure(true)
+        .http_only(true)
+        .same_site(SameSite::None)
+        .finish();
+
+    let cookie3 = Cookie::build("refreshToken", refresh_token.as_ref().unwrap())
+        .path("/api/v1/user")
+        .secure(true)
+        .http_only(true)
+        .same_site(SameSite::None)
+        .finish();
+
+    HttpResponse::Ok()
+        .cookie(cookie1)
+        .cookie(cookie2)
+        .cookie(cookie3)
+        .json(RefreshResponse {
+            access_token: access_token.unwrap(),
+            refresh_token: refresh_token.unwrap(),
+            user_id: user_data.id,
+        })
+}
@@ -0,0 +1,68 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::bots::generate_bot_token, middlewares::auth_middleware::UserData,
+    models::user::UserFromDB, responses::general_error::GeneralError,
+    routes::user::create_bot::BotWithTokenResponse, tokens::hash_token::hash_token,
+    validators::bot_id_type::BotId, AppState,
+};
+
+pub async fn regenerate_bot_token(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    bot_id_data: web::Json<BotId>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = bot_id_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    let token = generate_bot_token();
+
+    // the old token stops working right away, other users' bots are reported as missing
+    let updated_bot = sqlx::query_as::<_, UserFromDB>(
+        "update users set bot_token_hash = $1
+        where id = $2 and bot_owner_id = $3 and deleted_at is null
+        returning *",
+    )
+    .bind(hash_token(&token))
+    .bind(bot_id_data.0.bot_id)
+    .bind(user_data.user_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if updated_bot.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue updating the bot".to_string(),
+        });
+    }
+
+    if updated_bot.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Bot not found".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(BotWithTokenResponse {
+        bot: updated_bot.unwrap().unwrap(),
+        token,
+    })
+}
@@ -0,0 +1,152 @@
+use actix_web::{web, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::{email_tokens::consume_email_token, sessions::session_redis_key},
+    models::email_token::EmailTokenPurpose,
+    responses::general_error::GeneralError,
+    validators::reset_password_type::ResetPassword,
+    AppState,
+};
+
+pub async fn reset_password(
+    app_state: web::Data<AppState>,
+    reset_password_data: web::Json<ResetPassword>,
+) -> impl Responder {
+    if let Err(e) = reset_password_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let hashed_password = bcrypt::hash(&reset_password_data.0.new_password, 12);
+    if hashed_password.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue processing the password".to_string(),
+        });
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let consumed_token = consume_email_token(
+        transaction.as_mut(),
+        &reset_password_data.0.token,
+        EmailTokenPurpose::ResetPassword,
+    )
+    .await;
+
+    if consumed_token.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if consumed_token.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invalid or expired token".to_string(),
+        });
+    }
+
+    let user_id = consumed_token.unwrap().unwrap().user_id;
+
+    let update_result = sqlx::query_as::<_, (String,)>(
+        "update users set password = $1 where id = $2 and deleted_at is null returning username",
+    )
+    .bind(hashed_password.unwrap())
+    .bind(user_id)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if update_result.is_err() || update_result.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue updating the password".to_string(),
+        });
+    }
+
+    // whoever knew the old password is logged out everywhere
+    let revoke_sessions_result = sqlx::query_as::<_, (String,)>(
+        "update sessions set revoked_at = now()
+        where user_id = $1 and revoked_at is null
+        returning id",
+    )
+    .bind(user_id)
+    .fetch_all(transaction.as_mut())
+    .await;
+
+    let revoke_refresh_tokens_result = sqlx::query(
+        "update refresh_tokens set revoked_at = now()
+        where user_id = $1 and revoked_at is null",
+    )
+    .bind(user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    // a two factor login started with the old password can not be finished either
+    let expire_challenges_result = sqlx::query(
+        "update two_factor_challenges set used_at = now()
+        where user_id = $1 and used_at is null",
+    )
+    .bind(user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    // tokens made by whoever took over the account go too
+    let revoke_access_tokens_result = sqlx::query(
+        "update access_tokens set revoked_at = now()
+        where user_id = $1 and revoked_at is null",
+    )
+    .bind(user_id)
+    .execute(transaction.as_mut())
+    .await;
+
+    if revoke_sessions_result.is_err()
+        || revoke_refresh_tokens_result.is_err()
+        || expire_challenges_result.is_err()
+        || revoke_access_tokens_result.is_err()
+    {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the sessions".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    // proving access to the email is enough to lift a lockout on the account
+    let (username,) = update_result.unwrap().unwrap();
+    app_state
+        .login_throttle
+        .clear(&app_state.redis_pool, &username);
+
+    if let Ok(mut redis_conn_mut) = app_state.redis_pool.get() {
+        for (session_id,) in revoke_sessions_result.unwrap().iter() {
+            let _ = redis_conn_mut.del::<String, ()>(session_redis_key(user_id, session_id));
+        }
+    }
+
+    HttpResponse::Ok().json("password reset, login with the new password")
+}
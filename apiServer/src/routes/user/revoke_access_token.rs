@@ -0,0 +1,61 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    middlewares::auth_middleware::UserData, models::access_token::AccessTokenDb,
+    responses::general_error::GeneralError,
+    validators::revoke_access_token_type::RevokeAccessToken, AppState,
+};
+
+pub async fn revoke_access_token(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    revoke_access_token_data: web::Json<RevokeAccessToken>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = revoke_access_token_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    // other users' tokens are reported as missing, same as ones that never existed
+    let revoked_token = sqlx::query_as::<_, AccessTokenDb>(
+        "update access_tokens set revoked_at = now()
+        where id = $1 and user_id = $2 and revoked_at is null
+        returning *",
+    )
+    .bind(revoke_access_token_data.0.id)
+    .bind(user_data.user_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if revoked_token.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue revoking the access token".to_string(),
+        });
+    }
+
+    if revoked_token.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Access token not found".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(revoked_token.unwrap().unwrap())
+}
@@ -0,0 +1,59 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+
+use crate::{
+    dbcalls::sessions::revoke_session, middlewares::auth_middleware::UserData,
+    responses::general_error::GeneralError, AppState,
+};
+
+#[derive(serde::Serialize)]
+struct RevokedSessionsResponse {
+    revoked: usize,
+}
+
+pub async fn revoke_other_sessions(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if user_data.session_id.is_none() {
+        return HttpResponse::Forbidden().json(GeneralError {
+            message: "Only available to a login session".to_string(),
+        });
+    }
+    let current_session_id = user_data.session_id.clone().unwrap();
+
+    let other_sessions_result = sqlx::query_as::<_, (String,)>(
+        "select id from sessions where user_id = $1 and id <> $2 and revoked_at is null",
+    )
+    .bind(user_data.user_id)
+    .bind(&current_session_id)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if other_sessions_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    let other_sessions = other_sessions_result.unwrap();
+    for (session_id,) in other_sessions.iter() {
+        let revoke_result = revoke_session(&app_state, user_data.user_id, session_id).await;
+
+        if let Err(err_string) = revoke_result {
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: err_string,
+            });
+        }
+    }
+
+    HttpResponse::Ok().json(RevokedSessionsResponse {
+        revoked: other_sessions.len(),
+    })
+}
@@ -0,0 +1,85 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::sessions::revoke_session, middlewares::auth_middleware::UserData,
+    models::session::SessionDb, responses::general_error::GeneralError,
+    validators::revoke_session_type::RevokeSession, AppState,
+};
+
+pub async fn revoke_user_session(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    revoke_session_data: web::Json<RevokeSession>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = revoke_session_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+
+    if user_data.session_id.is_none() {
+        return HttpResponse::Forbidden().json(GeneralError {
+            message: "Only available to a login session".to_string(),
+        });
+    }
+    let current_session_id = user_data.session_id.clone().unwrap();
+
+    if revoke_session_data.0.session_id == current_session_id {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Use logout to end the current session".to_string(),
+        });
+    }
+
+    // other users' sessions are reported as missing, same as ones that never existed
+    let session_result = sqlx::query_as::<_, SessionDb>(
+        "select * from sessions where id = $1 and user_id = $2 and revoked_at is null",
+    )
+    .bind(&revoke_session_data.0.session_id)
+    .bind(user_data.user_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if session_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if session_result.unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Session not found".to_string(),
+        });
+    }
+
+    let revoke_result = revoke_session(
+        &app_state,
+        user_data.user_id,
+        &revoke_session_data.0.session_id,
+    )
+    .await;
+
+    if let Err(err_string) = revoke_result {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    HttpResponse::Ok().json("session revoked")
+}
@@ -0,0 +1,81 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    mailer::emails::send_verification_email,
+    middlewares::auth_middleware::UserData,
+    models::user::UserFromDB,
+    responses::general_error::GeneralError,
+    validators::email_type::{normalize_email, EmailAddress},
+    AppState,
+};
+
+pub async fn set_email(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    email_data: web::Json<EmailAddress>,
+) -> impl Responder {
+    if req.extensions().get::<UserData>().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if let Err(e) = email_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let user_data = req.extensions().get::<UserData>().unwrap().clone();
+    let email = normalize_email(&email_data.0.email);
+
+    // a new address starts unverified, setting the same one again just resends the link
+    let updated_user = sqlx::query_as::<_, UserFromDB>(
+        "update users set
+        email_verified_at = case when email = $1 then email_verified_at end,
+        email = $1
+        where id = $2 and deleted_at is null
+        returning *",
+    )
+    .bind(&email)
+    .bind(user_data.user_id)
+    .fetch_optional(&app_state.database)
+    .await;
+
+    if let Err(sqlx::Error::Database(database_error)) = &updated_user {
+        if database_error.constraint() == Some("users_email_key") {
+            return HttpResponse::BadRequest().json(GeneralError {
+                message: "Email already in use".to_string(),
+            });
+        }
+    }
+
+    if updated_user.is_err() || updated_user.as_ref().unwrap().is_none() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue updating the email".to_string(),
+        });
+    }
+
+    let updated_user = updated_user.unwrap().unwrap();
+
+    if updated_user.email_verified_at.is_some() {
+        return HttpResponse::Ok().json(updated_user);
+    }
+
+    if let Err(err_string) = send_verification_email(&app_state, user_data.user_id, &email).await {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    HttpResponse::Ok().json(updated_user)
+}
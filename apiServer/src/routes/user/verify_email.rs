@@ -0,0 +1,93 @@
+use actix_web::{web, HttpResponse, Responder};
+use validator::Validate;
+
+use crate::{
+    dbcalls::email_tokens::consume_email_token, models::email_token::EmailTokenPurpose,
+    models::user::UserFromDB, responses::general_error::GeneralError,
+    validators::email_token_type::EmailToken, AppState,
+};
+
+pub async fn verify_email(
+    app_state: web::Data<AppState>,
+    email_token_data: web::Json<EmailToken>,
+) -> impl Responder {
+    if let Err(e) = email_token_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    let consumed_token = consume_email_token(
+        transaction.as_mut(),
+        &email_token_data.0.token,
+        EmailTokenPurpose::VerifyEmail,
+    )
+    .await;
+
+    if consumed_token.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if consumed_token.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Invalid or expired token".to_string(),
+        });
+    }
+
+    let consumed_token = consumed_token.unwrap().unwrap();
+
+    // the link only proves the address it was sent to, not one set after it
+    let verified_user = sqlx::query_as::<_, UserFromDB>(
+        "update users set email_verified_at = now()
+        where id = $1 and email = $2 and deleted_at is null
+        returning *",
+    )
+    .bind(consumed_token.user_id)
+    .bind(&consumed_token.email)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    if verified_user.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue talking to the database".to_string(),
+        });
+    }
+
+    if verified_user.as_ref().unwrap().is_none() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "The email changed since this link was sent".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    HttpResponse::Ok().json(verified_user.unwrap().unwrap())
+}
@@ -0,0 +1,150 @@
+use actix_web::{web, HttpResponse, Responder};
+use redis::Commands;
+use validator::Validate;
+
+use crate::{
+    dbcalls::{
+        channel_permission::{check_channel_permission, ChannelPermission, PermissionCheck},
+        incoming_webhooks::find_active_incoming_webhook,
+        outgoing_webhooks::enqueue_message_created,
+    },
+    models::{message::MessagesDb, published_events::PublishedMessage},
+    responses::general_error::GeneralError,
+    validators::incoming_webhook_message_type::IncomingWebhookMessage,
+    AppState,
+};
+
+/// Posts into the webhook's channel without a login, the token in the url is the only credential.
+pub async fn post_incoming_webhook(
+    app_state: web::Data<AppState>,
+    token: web::Path<String>,
+    webhook_message_data: web::Json<IncomingWebhookMessage>,
+) -> impl Responder {
+    if let Err(e) = webhook_message_data.validate() {
+        let mut validation_errors: Vec<String> = Vec::new();
+        for (_, err) in e.field_errors().iter() {
+            if let Some(message) = &err[0].message {
+                validation_errors.push(message.clone().into_owned());
+            }
+        }
+        return HttpResponse::BadRequest().json(
+            crate::responses::validation_errors::ValidationErrorsToBeReturned {
+                errors: validation_errors,
+            },
+        );
+    }
+
+    let webhook_result = find_active_incoming_webhook(&app_state, &token.into_inner()).await;
+
+    if let Err(err_string) = webhook_result {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: err_string,
+        });
+    }
+
+    if webhook_result.as_ref().unwrap().is_none() {
+        return HttpResponse::NotFound().json(GeneralError {
+            message: "Webhook not found".to_string(),
+        });
+    }
+
+    let webhook = webhook_result.unwrap().unwrap();
+
+    let transaction_res = app_state.database.begin().await;
+    if transaction_res.is_err() {
+        return HttpResponse::BadRequest().json(GeneralError {
+            message: "Issue starting the transaction".to_string(),
+        });
+    }
+
+    let mut transaction = transaction_res.unwrap();
+
+    // the webhook posts on behalf of whoever created it, so it stops once they can not post
+    let permission_result = check_channel_permission(
+        transaction.as_mut(),
+        webhook.created_by,
+        webhook.channel_id,
+        ChannelPermission::SendMessages,
+    )
+    .await;
+
+    match permission_result {
+        Ok(PermissionCheck::Allowed(_)) => {}
+        Ok(PermissionCheck::Archived) => {
+            let _ = transaction.rollback().await;
+            return HttpResponse::BadRequest().json(GeneralError {
+                message: "This channel is archived".to_string(),
+            });
+        }
+        Ok(_) => {
+            let _ = transaction.rollback().await;
+            return HttpResponse::Unauthorized().json(GeneralError {
+                message: "The creator of this webhook can no longer post in this channel"
+                    .to_string(),
+            });
+        }
+        Err(err_string) => {
+            let _ = transaction.rollback().await;
+            return HttpResponse::InternalServerError().json(GeneralError {
+                message: err_string,
+            });
+        }
+    }
+
+    let send_message_result = sqlx::query_as::<_, MessagesDb>(
+        "insert into messages (sender_id, channel_id, message) values ($1, $2, $3) returning *",
+    )
+    .bind(webhook.integration_user_id)
+    .bind(webhook.channel_id)
+    .bind(&webhook_message_data.0.message)
+    .fetch_optional(transaction.as_mut())
+    .await;
+
+    let touch_result =
+        sqlx::query("update incoming_webhooks set last_used_at = now() where id = $1")
+            .bind(webhook.id)
+            .execute(transaction.as_mut())
+            .await;
+
+    if send_message_result.is_err()
+        || send_message_result.as_ref().unwrap().is_none()
+        || touch_result.is_err()
+    {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue sending the message".to_string(),
+        });
+    }
+
+    let new_message = send_message_result.unwrap().unwrap();
+
+    let enqueue_result = enqueue_message_created(transaction.as_mut(), new_message.id).await;
+
+    if enqueue_result.is_err() {
+        let _ = transaction.rollback().await;
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue sending the message".to_string(),
+        });
+    }
+
+    let commit_result = transaction.commit().await;
+    if commit_result.is_err() {
+        return HttpResponse::InternalServerError().json(GeneralError {
+            message: "Issue committing the transaction".to_string(),
+        });
+    }
+
+    let published_message = PublishedMessage::new(
+        new_message.id,
+        webhook_message_data.0.message,
+        webhook.integration_user_id,
+        new_message.created_at,
+        None,
+    );
+
+    if let Ok(mut redis_conn_mut) = app_state.redis_pool.get() {
+        let json_message = serde_json::to_string(&published_message).unwrap();
+        let _ = redis_conn_mut.publish::<i32, String, ()>(webhook.channel_id, json_message);
+    }
+    HttpResponse::Ok().json("message sent")
+}
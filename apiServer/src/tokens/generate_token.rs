@@ -1,21 +1,28 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+// access tokens are short lived, the refresh token is what keeps a user logged in
+pub const ACCESS_TOKEN_EXPIRY_IN_SECONDS: u64 = 900;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct Claims {
     pub username: String,
     pub user_id: i32,
+    pub session_id: String,
     pub exp: usize,
 }
 
 pub fn generate_token(
     username: &str,
     user_id: i32,
+    session_id: &str,
     access_token_secret: &str,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let claims = Claims {
         user_id,
         username: username.to_string(),
-        exp: (SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 86400) as usize,
+        session_id: session_id.to_string(),
+        exp: (SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs()
+            + ACCESS_TOKEN_EXPIRY_IN_SECONDS) as usize,
     };
     let header = jsonwebtoken::Header::default();
     let token = jsonwebtoken::encode(
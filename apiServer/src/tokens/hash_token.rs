@@ -0,0 +1,7 @@
+use sha2::{Digest, Sha256};
+
+/// Only the hash of an opaque token is kept server side, a leaked table or
+/// redis dump can not be replayed.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,2 +1,7 @@
+pub mod generate_random_token;
 pub mod generate_token;
+pub mod hash_token;
+pub mod refresh_token;
+pub mod totp;
 pub mod validate_token;
+pub mod webhook_signature;
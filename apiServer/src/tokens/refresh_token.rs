@@ -0,0 +1,147 @@
+use redis::Commands;
+
+use crate::{models::refresh_token::RefreshTokenDb, AppState};
+
+use super::{generate_random_token::generate_random_token, hash_token::hash_token};
+
+pub const REFRESH_TOKEN_EXPIRY_IN_SECONDS: i64 = 2592000; // 30 days
+
+pub enum RefreshTokenUse {
+    Rotated(RefreshTokenDb),
+    Reused(RefreshTokenDb),
+    Invalid,
+}
+
+fn redis_key(token_hash: &str) -> String {
+    format!("refresh:{}", token_hash)
+}
+
+/// Issues the next refresh token of a family, the family is the session it belongs to.
+pub async fn issue_refresh_token(
+    app_state: &AppState,
+    user_id: i32,
+    family_id: &str,
+) -> Result<String, String> {
+    let token = generate_random_token(64);
+    let token_hash = hash_token(&token);
+
+    let insert_result = sqlx::query_as::<_, (i32,)>(
+        "insert into refresh_tokens(user_id, family_id, token_hash, expires_at)
+        values ($1, $2, $3, now() + make_interval(secs => $4))
+        returning id",
+    )
+    .bind(user_id)
+    .bind(family_id)
+    .bind(&token_hash)
+    .bind(REFRESH_TOKEN_EXPIRY_IN_SECONDS as f64)
+    .fetch_one(&app_state.database)
+    .await;
+
+    if insert_result.is_err() {
+        return Err("Issue generating refresh token".to_string());
+    }
+
+    // the key only exists while the token is unspent, it points at the row
+    if let Ok(mut redis_connection) = app_state.redis_pool.get() {
+        let (token_id,) = insert_result.unwrap();
+        let _: Result<(), _> = redis_connection.set_ex(
+            redis_key(&token_hash),
+            token_id,
+            REFRESH_TOKEN_EXPIRY_IN_SECONDS as u64,
+        );
+    }
+
+    Ok(token)
+}
+
+/// Takes the row id of an unspent token out of redis, so only one caller gets it.
+fn take_cached_refresh_token(app_state: &AppState, token_hash: &str) -> Option<i32> {
+    let mut redis_connection = app_state.redis_pool.get().ok()?;
+    redis::cmd("GETDEL")
+        .arg(redis_key(token_hash))
+        .query::<Option<i32>>(&mut *redis_connection)
+        .ok()
+        .flatten()
+}
+
+/// Spends a refresh token, looking it up in redis first and falling back to
+/// Postgres when redis is down or lost the key. Postgres is the record of which
+/// tokens were already spent, which is what catches a replayed token.
+pub async fn consume_refresh_token(
+    app_state: &AppState,
+    token: &str,
+) -> Result<RefreshTokenUse, String> {
+    let token_hash = hash_token(token);
+
+    // the conditional updates make sure only one of two concurrent refreshes wins,
+    // and honour revocations that only went to Postgres
+    let consumed_result = match take_cached_refresh_token(app_state, &token_hash) {
+        Some(token_id) => {
+            sqlx::query_as::<_, RefreshTokenDb>(
+                "update refresh_tokens set used_at = now()
+                where id = $1 and used_at is null and revoked_at is null and expires_at > now()
+                returning *",
+            )
+            .bind(token_id)
+            .fetch_optional(&app_state.database)
+            .await
+        }
+        None => {
+            sqlx::query_as::<_, RefreshTokenDb>(
+                "update refresh_tokens set used_at = now()
+                where token_hash = $1 and used_at is null and revoked_at is null and expires_at > now()
+                returning *",
+            )
+            .bind(&token_hash)
+            .fetch_optional(&app_state.database)
+            .await
+        }
+    };
+
+    match consumed_result {
+        Err(_) => return Err("Issue talking to the database".to_string()),
+        Ok(Some(consumed_token)) => return Ok(RefreshTokenUse::Rotated(consumed_token)),
+        Ok(None) => {}
+    }
+
+    let existing_result =
+        sqlx::query_as::<_, RefreshTokenDb>("select * from refresh_tokens where token_hash = $1")
+            .bind(&token_hash)
+            .fetch_optional(&app_state.database)
+            .await;
+
+    match existing_result {
+        Err(_) => Err("Issue talking to the database".to_string()),
+        Ok(Some(existing_token)) if existing_token.used_at.is_some() => {
+            Ok(RefreshTokenUse::Reused(existing_token))
+        }
+        Ok(_) => Ok(RefreshTokenUse::Invalid),
+    }
+}
+
+/// Revokes every token that descends from the same login.
+pub async fn revoke_refresh_token_family(
+    app_state: &AppState,
+    family_id: &str,
+) -> Result<(), String> {
+    let revoked_result = sqlx::query_as::<_, (String,)>(
+        "update refresh_tokens set revoked_at = now()
+        where family_id = $1 and revoked_at is null
+        returning token_hash",
+    )
+    .bind(family_id)
+    .fetch_all(&app_state.database)
+    .await;
+
+    if revoked_result.is_err() {
+        return Err("Issue revoking the refresh tokens".to_string());
+    }
+
+    if let Ok(mut redis_connection) = app_state.redis_pool.get() {
+        for (token_hash,) in revoked_result.unwrap().iter() {
+            let _: Result<(), _> = redis_connection.del(redis_key(token_hash));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+const TOTP_ISSUER: &str = "Chat";
+const TOTP_DIGITS: usize = 6;
+const TOTP_STEP_IN_SECONDS: u64 = 30;
+// a code from the step before or after still counts, phone clocks drift
+const TOTP_SKEW_IN_STEPS: i64 = 1;
+
+/// A fresh 160 bit secret, base32 encoded the way authenticator apps take it.
+pub fn generate_totp_secret() -> String {
+    let mut secret = [0u8; 20];
+    rand::thread_rng().fill_bytes(&mut secret);
+    Secret::Raw(secret.to_vec()).to_encoded().to_string()
+}
+
+fn build_totp(secret: &str, username: &str) -> Result<TOTP, String> {
+    let secret = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|_| "Issue reading the two factor secret".to_string())?;
+
+    // skew is handled in matching_totp_step so the matched step is known
+    TOTP::new(
+        Algorithm::SHA1,
+        TOTP_DIGITS,
+        0,
+        TOTP_STEP_IN_SECONDS,
+        secret,
+        Some(TOTP_ISSUER.to_string()),
+        // the label uses ':' to separate the issuer from the account
+        username.replace(':', ""),
+    )
+    .map_err(|_| "Issue reading the two factor secret".to_string())
+}
+
+/// The otpauth:// uri authenticator apps scan to add the account.
+pub fn totp_uri(secret: &str, username: &str) -> Result<String, String> {
+    Ok(build_totp(secret, username)?.get_url())
+}
+
+/// The time step `code` was generated for, None when it matches no step in
+/// the window or only ones at or before `last_used_step`, so a code can not
+/// be replayed.
+pub fn matching_totp_step(
+    secret: &str,
+    username: &str,
+    code: &str,
+    last_used_step: Option<i64>,
+) -> Result<Option<i64>, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| "Issue reading the clock".to_string())?
+        .as_secs();
+    matching_totp_step_at(secret, username, code, last_used_step, now)
+}
+
+fn matching_totp_step_at(
+    secret: &str,
+    username: &str,
+    code: &str,
+    last_used_step: Option<i64>,
+    now: u64,
+) -> Result<Option<i64>, String> {
+    let totp = build_totp(secret, username)?;
+    let current_step = (now / TOTP_STEP_IN_SECONDS) as i64;
+
+    for step in current_step - TOTP_SKEW_IN_STEPS..=current_step + TOTP_SKEW_IN_STEPS {
+        if last_used_step.is_some_and(|last_used_step| step <= last_used_step) {
+            continue;
+        }
+        if totp.check(code, step as u64 * TOTP_STEP_IN_SECONDS) {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_totp, matching_totp_step_at, TOTP_STEP_IN_SECONDS};
+
+    // base32 of the rfc 6238 sha1 key "12345678901234567890"
+    const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    const NOW: u64 = 1_700_000_000;
+    const CURRENT_STEP: i64 = (NOW / TOTP_STEP_IN_SECONDS) as i64;
+
+    fn code_for_step(step: i64) -> String {
+        build_totp(SECRET, "alice")
+            .unwrap()
+            .generate(step as u64 * TOTP_STEP_IN_SECONDS)
+    }
+
+    #[test]
+    fn matches_the_rfc_6238_vector() {
+        // rfc 6238 appendix b, 8 digit 94287082 at 59 seconds, we use the last 6
+        assert_eq!(
+            matching_totp_step_at(SECRET, "alice", "287082", None, 59),
+            Ok(Some(1))
+        );
+    }
+
+    #[test]
+    fn accepts_one_step_of_skew_either_way() {
+        for step in [CURRENT_STEP - 1, CURRENT_STEP, CURRENT_STEP + 1] {
+            assert_eq!(
+                matching_totp_step_at(SECRET, "alice", &code_for_step(step), None, NOW),
+                Ok(Some(step))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_codes_outside_the_skew() {
+        for step in [CURRENT_STEP - 2, CURRENT_STEP + 2] {
+            assert_eq!(
+                matching_totp_step_at(SECRET, "alice", &code_for_step(step), None, NOW),
+                Ok(None)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_a_replayed_or_older_step() {
+        let code = code_for_step(CURRENT_STEP);
+        assert_eq!(
+            matching_totp_step_at(SECRET, "alice", &code, Some(CURRENT_STEP), NOW),
+            Ok(None)
+        );
+        assert_eq!(
+            matching_totp_step_at(
+                SECRET,
+                "alice",
+                &code_for_step(CURRENT_STEP - 1),
+                Some(CURRENT_STEP - 1),
+                NOW
+            ),
+            Ok(None)
+        );
+        // a later step than the last used one still works
+        assert_eq!(
+            matching_totp_step_at(SECRET, "alice", &code, Some(CURRENT_STEP - 1), NOW),
+            Ok(Some(CURRENT_STEP))
+        );
+    }
+}
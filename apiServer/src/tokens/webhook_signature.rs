@@ -0,0 +1,34 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Signs the timestamp and body together so a captured delivery can not be
+/// replayed later with a fresh timestamp.
+pub fn sign_webhook_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_webhook_payload;
+
+    #[test]
+    fn signs_timestamp_dot_body() {
+        // hmac-sha256("whsec_test", "1700000000.{\"event\":\"message_created\"}")
+        assert_eq!(
+            sign_webhook_payload("whsec_test", 1700000000, r#"{"event":"message_created"}"#),
+            "7dd5fa15bbe79046162327abe492507c2163fa0388be9e8ae0a15524b48bf01f"
+        );
+    }
+
+    #[test]
+    fn timestamp_is_part_of_the_signature() {
+        let body = r#"{"event":"message_created"}"#;
+        assert_ne!(
+            sign_webhook_payload("whsec_test", 1700000000, body),
+            sign_webhook_payload("whsec_test", 1700000001, body)
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct ArchiveChannel {
+    pub channel_id: i32,
+    pub archived: bool,
+}
@@ -0,0 +1,8 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct BotId {
+    #[serde(rename = "botId")]
+    #[validate(range(min = 1, message = "Bot id not given"))]
+    pub bot_id: i32,
+}
@@ -0,0 +1,19 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct ChangePassword {
+    #[serde(rename = "currentPassword")]
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Password should be between 6 and 20 length"
+    ))]
+    pub current_password: String,
+    #[serde(rename = "newPassword")]
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "New password should be between 6 and 20 length"
+    ))]
+    pub new_password: String,
+}
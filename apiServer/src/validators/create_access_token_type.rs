@@ -0,0 +1,17 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct CreateAccessToken {
+    #[validate(length(min = 1, max = 50, message = "Name should be between 1 and 50 length"))]
+    pub name: String,
+    #[validate(length(min = 1, message = "Pick at least one scope"))]
+    pub scopes: Vec<String>,
+    // left out for a token that never expires
+    #[serde(rename = "expiresInDays", default)]
+    #[validate(range(
+        min = 1,
+        max = 365,
+        message = "Expiry should be between 1 and 365 days"
+    ))]
+    pub expires_in_days: Option<i64>,
+}
@@ -9,4 +9,6 @@ pub struct Channel {
     ))]
     #[serde(rename = "channelName")]
     pub channel_name: String,
+    #[serde(rename = "isPublic", default)]
+    pub is_public: bool,
 }
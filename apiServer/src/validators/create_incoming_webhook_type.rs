@@ -0,0 +1,12 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct CreateIncomingWebhook {
+    pub channel_id: i32,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Webhook name should be between 1 and 50 length"
+    ))]
+    pub name: String,
+}
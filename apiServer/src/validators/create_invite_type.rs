@@ -0,0 +1,14 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct CreateInvite {
+    pub channel_id: i32,
+    #[validate(range(
+        min = 60,
+        max = 2592000,
+        message = "Expiry should be between 1 minute and 30 days"
+    ))]
+    pub expires_in_seconds: Option<i64>,
+    #[validate(range(min = 1, message = "Max uses should be at least 1"))]
+    pub max_uses: Option<i32>,
+}
@@ -0,0 +1,11 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct CreateOutgoingWebhook {
+    pub channel_id: i32,
+    #[validate(
+        url(message = "Webhook url is not valid"),
+        length(max = 2048, message = "Webhook url is too long")
+    )]
+    pub url: String,
+}
@@ -14,4 +14,9 @@ pub struct User {
         message = "Password should be between 6 and 20 length"
     ))]
     pub password: String,
+    #[validate(
+        email(message = "Email is not valid"),
+        length(max = 254, message = "Email can be at most 254 length")
+    )]
+    pub email: Option<String>,
 }
@@ -0,0 +1,6 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct DeleteChannel {
+    pub channel_id: i32,
+}
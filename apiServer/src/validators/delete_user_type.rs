@@ -0,0 +1,11 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct DeleteUser {
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Password should be between 6 and 20 length"
+    ))]
+    pub password: String,
+}
@@ -0,0 +1,11 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct DirectMessageType {
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Username should be between 6 and 20 length"
+    ))]
+    pub username: String,
+}
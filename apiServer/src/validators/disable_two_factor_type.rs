@@ -0,0 +1,13 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct DisableTwoFactor {
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Password should be between 6 and 20 length"
+    ))]
+    pub password: String,
+    #[validate(length(min = 6, max = 20, message = "Code should be between 6 and 20 length"))]
+    pub code: String,
+}
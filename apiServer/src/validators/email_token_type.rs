@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct EmailToken {
+    #[validate(length(min = 1, max = 64, message = "Token not given"))]
+    pub token: String,
+}
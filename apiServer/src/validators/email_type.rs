@@ -0,0 +1,15 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct EmailAddress {
+    #[validate(
+        email(message = "Email is not valid"),
+        length(max = 254, message = "Email can be at most 254 length")
+    )]
+    pub email: String,
+}
+
+/// Addresses are stored lowercased so the unique index catches case variants.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct IncomingWebhookMessage {
+    #[validate(length(min = 1, message = "Message not provided"))]
+    pub message: String,
+}
@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct InviteCode {
+    #[validate(length(min = 1, max = 32, message = "Invalid invite code"))]
+    pub code: String,
+}
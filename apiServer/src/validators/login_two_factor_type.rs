@@ -0,0 +1,11 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct LoginTwoFactor {
+    #[serde(rename = "challengeToken")]
+    #[validate(length(min = 1, max = 64, message = "Challenge token is not valid"))]
+    pub challenge_token: String,
+    // either a code from the authenticator app or a recovery code
+    #[validate(length(min = 6, max = 20, message = "Code should be between 6 and 20 length"))]
+    pub code: String,
+}
@@ -0,0 +1,20 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct LoginUser {
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Username should be between 6 and 20 length"
+    ))]
+    pub username: String,
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Password should be between 6 and 20 length"
+    ))]
+    pub password: String,
+    #[serde(rename = "deviceLabel", default)]
+    #[validate(length(max = 50, message = "Device label can be at most 50 length"))]
+    pub device_label: Option<String>,
+}
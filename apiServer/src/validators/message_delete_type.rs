@@ -0,0 +1,6 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate, Debug)]
+pub struct MessageDeleteType {
+    pub message_id: i32,
+}
@@ -0,0 +1,8 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate, Debug)]
+pub struct MessageEditType {
+    #[validate(length(min = 1, message = "Message not provided"))]
+    pub message: String,
+    pub message_id: i32,
+}
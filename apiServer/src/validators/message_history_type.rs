@@ -0,0 +1,9 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate, Debug)]
+pub struct MessageHistoryQuery {
+    pub before: Option<i32>,
+    pub after: Option<i32>,
+    #[validate(range(min = 1, max = 100, message = "Limit should be between 1 and 100"))]
+    pub limit: Option<i64>,
+}
@@ -5,4 +5,5 @@ pub struct MessageSendType {
     #[validate(length(min = 1, message = "Message not provided"))]
     pub message: String,
     pub channel_id: i32,
+    pub parent_id: Option<i32>,
 }
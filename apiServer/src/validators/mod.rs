@@ -1,6 +1,42 @@
 pub mod add_user_to_channel_type;
+pub mod archive_channel_type;
+pub mod bot_id_type;
+pub mod change_password_type;
+pub mod create_access_token_type;
+pub mod create_bot_type;
 pub mod create_channel_type;
+pub mod create_incoming_webhook_type;
+pub mod create_invite_type;
+pub mod create_outgoing_webhook_type;
 pub mod create_user_type;
+pub mod delete_channel_type;
+pub mod delete_user_type;
+pub mod direct_message_type;
+pub mod disable_two_factor_type;
+pub mod email_token_type;
+pub mod email_type;
 pub mod get_my_channels;
+pub mod get_socket_bot_type;
 pub mod get_socket_user_type;
+pub mod incoming_webhook_message_type;
+pub mod invite_code_type;
+pub mod join_channel_type;
+pub mod leave_channel_type;
+pub mod login_two_factor_type;
+pub mod login_user_type;
+pub mod message_delete_type;
+pub mod message_edit_type;
+pub mod message_history_type;
 pub mod message_type;
+pub mod public_channels_query_type;
+pub mod reaction_type;
+pub mod refresh_token_type;
+pub mod remove_member_type;
+pub mod reset_password_type;
+pub mod revoke_access_token_type;
+pub mod revoke_session_type;
+pub mod transfer_ownership_type;
+pub mod two_factor_code_type;
+pub mod update_channel_type;
+pub mod update_member_role_type;
+pub mod webhook_id_type;
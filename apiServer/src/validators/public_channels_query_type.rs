@@ -0,0 +1,10 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate, Debug)]
+pub struct PublicChannelsQuery {
+    #[validate(length(max = 20, message = "Search should be at most 20 length"))]
+    pub search: Option<String>,
+    pub after: Option<i32>,
+    #[validate(range(min = 1, max = 100, message = "Limit should be between 1 and 100"))]
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,8 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate, Debug)]
+pub struct ReactionType {
+    #[validate(length(min = 1, max = 32, message = "Emoji should be between 1 and 32 length"))]
+    pub emoji: String,
+    pub message_id: i32,
+}
@@ -0,0 +1,8 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct RefreshTokenRequest {
+    #[serde(rename = "refreshToken")]
+    #[validate(length(min = 1, message = "Refresh token not given"))]
+    pub refresh_token: String,
+}
@@ -0,0 +1,12 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct RemoveMember {
+    pub channel_id: i32,
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Username should be between 6 and 20 length"
+    ))]
+    pub username: String,
+}
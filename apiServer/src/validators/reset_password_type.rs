@@ -0,0 +1,14 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct ResetPassword {
+    #[validate(length(min = 1, max = 64, message = "Token not given"))]
+    pub token: String,
+    #[serde(rename = "newPassword")]
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "New password should be between 6 and 20 length"
+    ))]
+    pub new_password: String,
+}
@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct RevokeAccessToken {
+    #[validate(range(min = 1, message = "Access token id not given"))]
+    pub id: i32,
+}
@@ -0,0 +1,8 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct RevokeSession {
+    #[serde(rename = "sessionId")]
+    #[validate(length(min = 1, max = 32, message = "Session id not given"))]
+    pub session_id: String,
+}
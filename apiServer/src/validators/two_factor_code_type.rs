@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct TwoFactorCode {
+    #[validate(length(equal = 6, message = "Code should be 6 digits"))]
+    pub code: String,
+}
@@ -0,0 +1,17 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct UpdateChannel {
+    pub channel_id: i32,
+    #[validate(length(
+        min = 1,
+        max = 20,
+        message = "Channel name should be between 1 and 20 length"
+    ))]
+    #[serde(rename = "channelName")]
+    pub channel_name: Option<String>,
+    #[validate(length(max = 250, message = "Topic should be at most 250 length"))]
+    pub topic: Option<String>,
+    #[validate(length(max = 1000, message = "Description should be at most 1000 length"))]
+    pub description: Option<String>,
+}
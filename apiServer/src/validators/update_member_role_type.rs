@@ -0,0 +1,15 @@
+use validator::Validate;
+
+use crate::models::membership::ChannelRole;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct UpdateMemberRole {
+    pub channel_id: i32,
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Username should be between 6 and 20 length"
+    ))]
+    pub username: String,
+    pub role: ChannelRole,
+}
@@ -0,0 +1,7 @@
+use validator::Validate;
+
+#[derive(serde::Deserialize, serde::Serialize, Validate)]
+pub struct WebhookId {
+    #[validate(range(min = 1, message = "Webhook id not given"))]
+    pub webhook_id: i32,
+}
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use log::warn;
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    dbcalls::outgoing_webhooks::{
+        claim_due_deliveries, fail_revoked_deliveries, mark_attempt_failed, mark_delivered,
+        DueDelivery,
+    },
+    tokens::webhook_signature::sign_webhook_payload,
+};
+
+const POLL_INTERVAL_IN_SECONDS: u64 = 2;
+const BATCH_SIZE: i64 = 20;
+const REQUEST_TIMEOUT_IN_SECONDS: u64 = 10;
+// longer than a request can take, so a claimed delivery is never sent twice at once
+const LEASE_IN_SECONDS: f64 = 60.0;
+// with the doubling below a delivery is given up a little over four hours after the first attempt
+const MAX_ATTEMPTS: i32 = 10;
+const FIRST_RETRY_IN_SECONDS: i64 = 30;
+const MAX_RETRY_IN_SECONDS: i64 = 4 * 60 * 60;
+
+/// Seconds to wait after the nth failed attempt, doubling up to the cap.
+fn backoff_seconds(attempts: i32) -> i64 {
+    // a plain shift would silently drop the high bits instead of overflowing
+    2i64.checked_pow((attempts - 1).max(0) as u32)
+        .and_then(|factor| FIRST_RETRY_IN_SECONDS.checked_mul(factor))
+        .unwrap_or(i64::MAX)
+        .min(MAX_RETRY_IN_SECONDS)
+}
+
+/// The backoff after the nth failed attempt, `None` once the delivery is out of attempts.
+fn retry_delay_seconds(attempts: i32) -> Option<i64> {
+    if attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+    Some(backoff_seconds(attempts))
+}
+
+/// Polls the delivery queue for as long as the server runs.
+pub async fn run_delivery_worker(database: Pool<Postgres>) {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_IN_SECONDS))
+        // a redirect would send the signed payload somewhere the admin never registered
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Issue building the webhook http client");
+
+    loop {
+        if let Err(err) = fail_revoked_deliveries(&database).await {
+            warn!("Issue clearing deliveries of revoked webhooks: {}", err);
+        }
+
+        match claim_due_deliveries(&database, BATCH_SIZE, LEASE_IN_SECONDS).await {
+            Ok(deliveries) => {
+                for delivery in deliveries {
+                    let database = database.clone();
+                    let client = client.clone();
+                    actix_web::rt::spawn(async move {
+                        deliver(&database, &client, delivery).await;
+                    });
+                }
+            }
+            Err(err) => warn!("Issue claiming webhook deliveries: {}", err),
+        }
+
+        actix_web::rt::time::sleep(Duration::from_secs(POLL_INTERVAL_IN_SECONDS)).await;
+    }
+}
+
+async fn deliver(database: &Pool<Postgres>, client: &reqwest::Client, delivery: DueDelivery) {
+    let body = delivery.payload.to_string();
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign_webhook_payload(&delivery.secret, timestamp, &body);
+
+    let response = client
+        .post(&delivery.url)
+        .header("Content-Type", "application/json")
+        .header("X-Webhook-Delivery", delivery.id.to_string())
+        .header("X-Webhook-Event", &delivery.event)
+        .header("X-Webhook-Timestamp", timestamp.to_string())
+        .header("X-Webhook-Signature", format!("sha256={}", signature))
+        .body(body)
+        .send()
+        .await;
+
+    let attempts = delivery.attempts + 1;
+
+    let update_result = match response {
+        Ok(response) if response.status().is_success() => {
+            mark_delivered(database, delivery.id, response.status().as_u16() as i32).await
+        }
+        Ok(response) => {
+            mark_attempt_failed(
+                database,
+                delivery.id,
+                Some(response.status().as_u16() as i32),
+                &format!("Endpoint answered with {}", response.status()),
+                retry_delay_seconds(attempts),
+            )
+            .await
+        }
+        Err(err) => {
+            mark_attempt_failed(
+                database,
+                delivery.id,
+                None,
+                &err.to_string(),
+                retry_delay_seconds(attempts),
+            )
+            .await
+        }
+    };
+
+    // the lease runs out and the delivery is simply attempted again
+    if let Err(err) = update_result {
+        warn!("Issue recording webhook delivery {}: {}", delivery.id, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{backoff_seconds, retry_delay_seconds, MAX_ATTEMPTS, MAX_RETRY_IN_SECONDS};
+
+    #[test]
+    fn retry_delay_doubles_after_each_attempt() {
+        let delays: Vec<Option<i64>> = (1..MAX_ATTEMPTS).map(retry_delay_seconds).collect();
+        assert_eq!(
+            delays,
+            [30, 60, 120, 240, 480, 960, 1920, 3840, 7680].map(Some)
+        );
+    }
+
+    #[test]
+    fn retry_delay_gives_up_after_max_attempts() {
+        assert_eq!(retry_delay_seconds(MAX_ATTEMPTS), None);
+        assert_eq!(retry_delay_seconds(MAX_ATTEMPTS + 1), None);
+        assert_eq!(retry_delay_seconds(i32::MAX), None);
+    }
+
+    #[test]
+    fn retry_delay_before_any_attempt_is_the_first_delay() {
+        assert_eq!(retry_delay_seconds(0), Some(30));
+        assert_eq!(retry_delay_seconds(-5), Some(30));
+    }
+
+    #[test]
+    fn backoff_stops_at_the_cap_without_overflowing() {
+        assert_eq!(backoff_seconds(10), MAX_RETRY_IN_SECONDS);
+        // 30 << 59 wraps around, the doubling must not
+        assert_eq!(backoff_seconds(60), MAX_RETRY_IN_SECONDS);
+        assert_eq!(backoff_seconds(64), MAX_RETRY_IN_SECONDS);
+        assert_eq!(backoff_seconds(i32::MAX), MAX_RETRY_IN_SECONDS);
+    }
+}
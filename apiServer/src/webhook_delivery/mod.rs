@@ -0,0 +1 @@
+pub mod delivery_worker;
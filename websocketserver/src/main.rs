@@ -1,4 +1,5 @@
 use axum::extract::State;
+use axum::http::{header, HeaderMap};
 use axum::routing::get;
 use axum::{extract::WebSocketUpgrade, response::IntoResponse, Router};
 use futures_util::StreamExt;
@@ -20,9 +21,18 @@ pub mod managers;
 
 async fn websocket_handler(
     ws: WebSocketUpgrade,
+    headers: HeaderMap,
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| managers::handle_websocket::handle_socket(socket, state))
+    // bots connect with their token in the handshake instead of sending a JoinMessage
+    let bot_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| token.starts_with("bot_"))
+        .map(|token| token.to_string());
+
+    ws.on_upgrade(move |socket| managers::handle_websocket::handle_socket(socket, state, bot_token))
 }
 
 #[tokio::main]
@@ -51,6 +61,7 @@ async fn main() {
 
     let channel_manager = Arc::new(Mutex::new(ChannelManager::new()));
     let cloned_channel_manager = channel_manager.clone();
+    let cloned_redis_subscription_struct = redis_subscription_struct.clone();
     tokio::spawn(async move {
         loop {
             let message = pubsub_stream.next().await.expect("Invalid message");
@@ -64,6 +75,7 @@ async fn main() {
                 .send_message(
                     message.get_channel_name().parse().expect("Invalid channel"),
                     &string_message,
+                    cloned_redis_subscription_struct.clone(),
                 )
                 .await;
         }
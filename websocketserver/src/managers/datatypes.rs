@@ -8,13 +8,19 @@ use futures_util::sink::SinkExt;
 use futures_util::stream::SplitSink;
 use tokio::sync::{Mutex, RwLock};
 
+// every payload is forwarded to the clients as the whole json object, only
+// the fields needed for routing are read here
 #[derive(serde::Deserialize)]
 struct MessageToBeBroadcasted {
-    message: String,
-    sender: i32,
+    // plain chat messages carry no event
+    #[serde(default)]
+    event: Option<String>,
+    // the member affected by a membership event
+    #[serde(default)]
+    user_id: Option<i32>,
 }
 
-use super::{subscribe_connection::RedisPubSub, unsubscribe_connection::unsubscribe_from_redis};
+use super::subscribe_connection::RedisPubSub;
 
 #[derive(Debug)]
 pub struct Connection {
@@ -26,7 +32,14 @@ pub struct UserId(i32);
 
 pub struct ChannelManager {
     pub channels: HashMap<i32, HashSet<UserId>>,
-    pub connections: HashMap<UserId, Connection>,
+    // a user can be connected from several devices, one connection per session
+    pub connections: HashMap<UserId, Vec<Connection>>,
+}
+
+impl Default for ChannelManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ChannelManager {
@@ -39,9 +52,15 @@ impl ChannelManager {
 }
 
 impl ChannelManager {
-    pub async fn user_connected(&self, user_id: i32) -> bool {
-        if self.connections.contains_key(&UserId(user_id)) {
-            return true;
+    pub async fn connection_registered(
+        &self,
+        user_id: i32,
+        websocket_sender: &Arc<RwLock<SplitSink<WebSocket, Message>>>,
+    ) -> bool {
+        if let Some(user_connections) = self.connections.get(&UserId(user_id)) {
+            return user_connections
+                .iter()
+                .any(|conn| Arc::ptr_eq(&conn.sender, websocket_sender));
         }
         false
     }
@@ -53,20 +72,20 @@ impl ChannelManager {
         websocket_sender: Arc<RwLock<SplitSink<WebSocket, Message>>>,
         redis_subscription_struct: Arc<Mutex<RedisPubSub>>,
     ) {
-        if !self.user_connected(user_id).await {
+        if !self.connection_registered(user_id, &websocket_sender).await {
             let user_id = UserId(user_id);
             for channel_id in channel_ids.iter() {
                 self.channels
                     .entry(*channel_id)
-                    .or_insert_with(HashSet::new)
+                    .or_default()
                     .insert(user_id.clone());
             }
-            self.connections.insert(
-                user_id,
-                Connection {
+            self.connections
+                .entry(user_id)
+                .or_default()
+                .push(Connection {
                     sender: websocket_sender,
-                },
-            );
+                });
             {
                 redis_subscription_struct
                     .lock()
@@ -79,15 +98,30 @@ impl ChannelManager {
 }
 
 impl ChannelManager {
-    pub async fn remove_user(&mut self, connection: &Arc<RwLock<SplitSink<WebSocket, Message>>>) {
+    pub async fn remove_user(
+        &mut self,
+        connection: &Arc<RwLock<SplitSink<WebSocket, Message>>>,
+        redis_subscription_struct: Arc<Mutex<RedisPubSub>>,
+    ) {
         let user_id_to_be_removed = {
             self.connections
                 .iter()
-                .find(|(_, conn)| Arc::ptr_eq(&conn.sender, &connection))
+                .find(|(_, user_connections)| {
+                    user_connections
+                        .iter()
+                        .any(|conn| Arc::ptr_eq(&conn.sender, connection))
+                })
                 .map(|(user_id, _)| UserId(user_id.0))
                 .unwrap_or(UserId(-1))
         };
         {
+            if let Some(user_connections) = self.connections.get_mut(&user_id_to_be_removed) {
+                user_connections.retain(|conn| !Arc::ptr_eq(&conn.sender, connection));
+                // the user's other devices keep the channel subscriptions alive
+                if !user_connections.is_empty() {
+                    return;
+                }
+            }
             self.connections.remove(&user_id_to_be_removed);
         }
         let mut channels_to_remove = Vec::new();
@@ -101,31 +135,75 @@ impl ChannelManager {
             for channel in channels_to_remove.iter() {
                 self.channels.remove(channel);
             }
-            unsubscribe_from_redis(channels_to_remove).await;
+            redis_subscription_struct
+                .lock()
+                .await
+                .unsubscribe(channels_to_remove)
+                .await;
         }
     }
 }
 
 impl ChannelManager {
-    pub async fn send_message(&self, channel_id: i32, message: &str) {
+    pub async fn remove_user_from_channel(
+        &mut self,
+        user_id: i32,
+        channel_id: i32,
+        redis_subscription_struct: Arc<Mutex<RedisPubSub>>,
+    ) {
+        if let Some(user_set) = self.channels.get_mut(&channel_id) {
+            user_set.remove(&UserId(user_id));
+            if user_set.is_empty() {
+                self.channels.remove(&channel_id);
+                redis_subscription_struct
+                    .lock()
+                    .await
+                    .unsubscribe(vec![channel_id])
+                    .await;
+            }
+        }
+    }
+}
+
+impl ChannelManager {
+    pub async fn send_message(
+        &mut self,
+        channel_id: i32,
+        message: &str,
+        redis_subscription_struct: Arc<Mutex<RedisPubSub>>,
+    ) {
         let parsed_message: MessageToBeBroadcasted =
             serde_json::from_str(message).expect("Failed to parse JSON");
         let users = self.channels.get(&channel_id);
-        if users.is_some() {
-            for user_to_send_message_to in users.unwrap().iter() {
-                if user_to_send_message_to.0 != parsed_message.sender {
-                    let connection = self.connections.get(user_to_send_message_to).unwrap();
-                    let sender = &connection.sender;
-                    sender
+        if let Some(users) = users {
+            // the sender's devices get it too, the one that sent it can match the message_id
+            for user_to_send_message_to in users.iter() {
+                let user_connections = self.connections.get(user_to_send_message_to).unwrap();
+                for connection in user_connections.iter() {
+                    // a closed socket is cleaned up by its own handler, the rest still get the event
+                    let _ = connection
+                        .sender
                         .write()
                         .await
-                        .send(axum::extract::ws::Message::Text(Utf8Bytes::from(
-                            &parsed_message.message,
-                        )))
-                        .await
-                        .unwrap();
+                        .send(axum::extract::ws::Message::Text(Utf8Bytes::from(message)))
+                        .await;
                 }
             }
         }
+
+        // the removed member got the event above, after this they stop receiving the channel
+        if parsed_message.event.as_deref() == Some("member_removed") {
+            if let Some(user_id) = parsed_message.user_id {
+                self.remove_user_from_channel(user_id, channel_id, redis_subscription_struct)
+                    .await;
+            }
+        } else if parsed_message.event.as_deref() == Some("channel_deleted") {
+            self.channels.remove(&channel_id);
+            redis_subscription_struct
+                .lock()
+                .await
+                .unsubscribe(vec![channel_id])
+                .await;
+        }
     }
 }
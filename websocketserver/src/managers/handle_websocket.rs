@@ -5,13 +5,37 @@ use tokio::sync::RwLock;
 
 use crate::{managers::message_type_check::JoinMessage, AppState};
 
-use super::{get_channels::get_channels, validate_user::validate_user};
+use super::{get_channels::get_channels, validate_bot::validate_bot, validate_user::validate_user};
 
 use futures_util::{sink::SinkExt, stream::StreamExt};
 
-pub async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
+pub async fn handle_socket(socket: WebSocket, state: Arc<AppState>, bot_token: Option<String>) {
     let (sender, mut receiver) = socket.split();
     let sender = Arc::new(RwLock::new(sender));
+    if let Some(bot_token) = bot_token {
+        let bot_id = validate_bot(bot_token, &state.api_secret).await;
+        if bot_id.is_none() {
+            let _ = sender
+                .write()
+                .await
+                .send(axum::extract::ws::Message::Close(None))
+                .await;
+            return;
+        }
+        let bot_id = bot_id.unwrap();
+        let channels = get_channels(bot_id, &state.api_secret).await;
+        state
+            .channel_user_map
+            .lock()
+            .await
+            .add_user(
+                bot_id,
+                channels.unwrap_or_default(),
+                sender.clone(),
+                state.redis_pub_sub_handler_struct.clone(),
+            )
+            .await;
+    }
     while let Some(Ok(msg)) = receiver.next().await {
         match msg {
             axum::extract::ws::Message::Text(text_message) => {
@@ -38,7 +62,7 @@ pub async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
 								.await;
 							},
 							crate::managers::message_type_check::IncomingMessageFromUser::LeaveMessage => {
-								state.channel_user_map.lock().await.remove_user(&sender).await;
+								state.channel_user_map.lock().await.remove_user(&sender, state.redis_pub_sub_handler_struct.clone()).await;
 								let _ = sender.write().await.flush().await;
 								drop(sender);
 								break;
@@ -65,7 +89,7 @@ pub async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                     .channel_user_map
                     .lock()
                     .await
-                    .remove_user(&sender)
+                    .remove_user(&sender, state.redis_pub_sub_handler_struct.clone())
                     .await;
                 let _ = sender.write().await.flush().await;
                 drop(sender);
@@ -4,4 +4,5 @@ pub mod handle_websocket;
 pub mod message_type_check;
 pub mod subscribe_connection;
 pub mod unsubscribe_connection;
+pub mod validate_bot;
 pub mod validate_user;
@@ -1,4 +1,19 @@
-pub async fn unsubscribe_from_redis(channels: Vec<i32>) {
-    println!("Unsubscribing");
-    println!("{:?}", channels);
+use super::subscribe_connection::RedisPubSub;
+
+impl RedisPubSub {
+    pub async fn unsubscribe(&mut self, channels_to_unsubscribe: Vec<i32>) -> bool {
+        {
+            for channel in channels_to_unsubscribe.iter() {
+                if self.subscribed_channels.contains(channel) {
+                    let unsubscription_res = self.pubsub_sink.unsubscribe(channel).await;
+                    if unsubscription_res.is_err() {
+                        println!("Issue unsubscribing from {:?}", channel);
+                    } else {
+                        self.subscribed_channels.remove(channel);
+                    }
+                }
+            }
+        }
+        true
+    }
 }
@@ -0,0 +1,23 @@
+use reqwest::Client;
+use serde_json::json;
+
+pub async fn validate_bot(token: String, api_secret: &str) -> Option<i32> {
+    let client = Client::new();
+    let url = "http://localhost:8000/websocket/isValidBot";
+    let body = json!({
+        "token": token,
+        "endpoint_secret": api_secret
+    });
+
+    let response = client.post(url).json(&body).send().await;
+    if response.is_err() {
+        return None;
+    }
+
+    let response_body = response.unwrap().json().await;
+    if response_body.is_err() {
+        return None;
+    }
+
+    response_body.unwrap()
+}